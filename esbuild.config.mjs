@@ -1,6 +1,7 @@
 import esbuild from "esbuild";
 import process from "process";
 import builtins from "builtin-modules";
+import { execSync } from "child_process";
 
 const banner =
     `/*
@@ -11,6 +12,14 @@ if you want to view the source, please visit the github repository of this plugi
 
 const prod = (process.argv[2] === 'production');
 
+// Build info shown in the settings tab; empty when git is unavailable (the plugin falls back to "unknown")
+let gitSha = '';
+try {
+    gitSha = execSync('git rev-parse --short HEAD', { stdio: ['ignore', 'pipe', 'ignore'] }).toString().trim();
+} catch {
+    // Not a git checkout
+}
+
 const context = await esbuild.context({
     banner: {
         js: banner,
@@ -37,6 +46,10 @@ const context = await esbuild.context({
     logLevel: "info",
     sourcemap: prod ? false : 'inline',
     treeShaking: true,
+    define: {
+        __GIT_SHA__: JSON.stringify(gitSha),
+        __BUILT_AT__: JSON.stringify(new Date().toISOString()),
+    },
     outfile: 'main.js',
 });
 
@@ -221,8 +221,9 @@ const SmartCard: React.FC<{ result: SearchResult }> = ({
 }) => {
     const [showPreview, setShowPreview] = useState(false);
 
-    // Extract file name from path
-    const filePath = result.notePath.split("/").pop() || result.notePath;
+    // Prefer the note's display title, else the file name from the path
+    const label =
+        result.noteTitle || result.notePath.split("/").pop() || result.notePath;
     const excerpt = result.excerpt || "无预览";
 
     return (
@@ -241,7 +242,7 @@ const SmartCard: React.FC<{ result: SearchResult }> = ({
                 <span className="memo-echo-score-badge">
                     {Math.round(result.similarity * 100)}
                 </span>
-                <span className="memo-echo-file-link">{filePath}</span>
+                <span className="memo-echo-file-link">{label}</span>
             </div>
             <div className="memo-echo-item-text">{excerpt}</div>
 
@@ -54,6 +54,12 @@ export enum VECTOR_NAMES {
 	TITLE = "title_vec",
 }
 
+/**
+ * Named multivector with one content vector per paragraph, scored by max-sim (late interaction)
+ * Only present in collections created with `multiVector` enabled
+ */
+export const CONTENT_MULTI_VECTOR = "content_multi_vec";
+
 /**
  * Default fusion weights
  */
@@ -71,6 +77,31 @@ export const SEARCH_DEFAULTS = {
 	prefetchMultiplier: 2, // Fetch 2x more for fusion
 } as const;
 
+/**
+ * Query words that carry no meaning on their own (English and Chinese function words)
+ */
+export const DEFAULT_STOP_WORDS = [
+	"a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "in", "is", "it",
+	"of", "on", "or", "that", "the", "this", "to", "was", "with",
+	"的", "了", "和", "是", "在", "我", "有", "就", "不", "人", "都", "一", "也", "很", "到", "说", "要", "去", "你", "这", "那", "吗", "呢", "吧", "啊",
+];
+
+/**
+ * Payload fields indexed on collection init, used by filters
+ */
+export type PayloadIndexType = "keyword" | "integer";
+
+export const DEFAULT_PAYLOAD_INDEXES: Record<string, PayloadIndexType> = {
+	_customId: "keyword",
+	filePath: "keyword",
+	filePathKey: "keyword",
+	type: "keyword",
+	tags: "keyword",
+	folders: "keyword",
+	indexedAt: "integer",
+	depth: "integer",
+};
+
 /**
  * View Type Constants - Obsidian View registration
  */
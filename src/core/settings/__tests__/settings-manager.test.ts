@@ -12,6 +12,9 @@ import {
     DEFAULT_CONCEPT_EXTRACTION_CONFIG,
     DEFAULT_CONCEPT_FE_CONFIG,
     DEFAULT_CONCEPT_SKIP_CONFIG,
+    DEFAULT_QDRANT_TUNING_CONFIG,
+    DEFAULT_INDEXING_CONFIG,
+    DEFAULT_SEARCH_CONFIG,
 } from '../../types/setting';
 import type { ServiceUpdaters } from '../types';
 
@@ -21,9 +24,23 @@ const mockSettings: MemoEchoSettings = {
     embeddingConfig: { ...DEFAULT_EMBEDDING_CONFIG },
     llmConfig: { ...DEFAULT_LLM_CONFIG },
 
+    vectorBackend: 'qdrant',
+
     // Qdrant settings
     qdrantUrl: 'http://localhost:6333',
     qdrantCollection: 'obsidian_notes',
+    qdrantTimeoutMs: 10000,
+    qdrantConnectRetries: 3,
+    qdrantTuning: { ...DEFAULT_QDRANT_TUNING_CONFIG },
+    embeddingKeepAlive: '30m',
+    embeddingHeaders: {},
+    embeddingMaxInputChars: 8000,
+
+    // Indexing config
+    indexing: { ...DEFAULT_INDEXING_CONFIG },
+
+    // Search config
+    search: { ...DEFAULT_SEARCH_CONFIG },
 
     // Concept extraction configs
     conceptExtraction: { ...DEFAULT_CONCEPT_EXTRACTION_CONFIG },
@@ -38,11 +55,15 @@ const mockSettings: MemoEchoSettings = {
 // Mock service updaters
 const mockServiceUpdaters: ServiceUpdaters = {
     embedding: vi.fn(),
+    embeddingRequest: vi.fn(),
     llm: vi.fn(),
     conceptExtraction: vi.fn(),
     conceptExtractionSettings: vi.fn(),
     conceptFE: vi.fn(),
     conceptSkip: vi.fn(),
+    qdrantTuning: vi.fn(),
+    indexing: vi.fn(),
+    search: vi.fn(),
 };
 
 const mockSaveSettings = vi.fn();
@@ -61,6 +82,12 @@ describe('SettingsManager', () => {
         mockSettings.conceptExtraction = { ...DEFAULT_CONCEPT_EXTRACTION_CONFIG };
         mockSettings.conceptFE = { ...DEFAULT_CONCEPT_FE_CONFIG };
         mockSettings.conceptSkip = { ...DEFAULT_CONCEPT_SKIP_CONFIG };
+        mockSettings.qdrantTuning = { ...DEFAULT_QDRANT_TUNING_CONFIG };
+        mockSettings.indexing = { ...DEFAULT_INDEXING_CONFIG };
+        mockSettings.search = { ...DEFAULT_SEARCH_CONFIG };
+        mockSettings.embeddingKeepAlive = '30m';
+        mockSettings.embeddingHeaders = {};
+        mockSettings.embeddingMaxInputChars = 8000;
 
         // Create new SettingsManager instance
         settingsManager = new SettingsManager(
@@ -109,6 +136,51 @@ describe('SettingsManager', () => {
         });
     });
 
+    describe('Embedding Request Settings', () => {
+        it('should update keep-alive through the service updater', async () => {
+            const result = await settingsManager.updateEmbeddingRequest({ embeddingKeepAlive: '1h30m' });
+
+            expect(result.success).toBe(true);
+            expect(mockSettings.embeddingKeepAlive).toBe('1h30m');
+            expect(mockServiceUpdaters.embeddingRequest).toHaveBeenCalledWith({ embeddingKeepAlive: '1h30m' });
+            expect(mockSaveSettings).toHaveBeenCalled();
+        });
+
+        it('should reject a malformed keep-alive', async () => {
+            const result = await settingsManager.updateEmbeddingRequest({ embeddingKeepAlive: '30 minutes' });
+
+            expect(result.success).toBe(false);
+            expect(result.errors?.[0].field).toBe('embeddingKeepAlive');
+            expect(mockServiceUpdaters.embeddingRequest).not.toHaveBeenCalled();
+        });
+
+        it('should accept valid headers and reject names that are not HTTP tokens', async () => {
+            const valid = await settingsManager.updateEmbeddingRequest({
+                embeddingHeaders: { Authorization: 'Bearer abc', 'X-Api-Key': 'k' },
+            });
+            expect(valid.success).toBe(true);
+            expect(mockSettings.embeddingHeaders).toEqual({ Authorization: 'Bearer abc', 'X-Api-Key': 'k' });
+
+            const invalid = await settingsManager.updateEmbeddingRequest({
+                embeddingHeaders: { 'X Api Key': 'k' },
+            });
+            expect(invalid.success).toBe(false);
+            expect(invalid.errors?.[0].field).toBe('embeddingHeaders');
+            expect(mockSettings.embeddingHeaders).toEqual({ Authorization: 'Bearer abc', 'X-Api-Key': 'k' });
+        });
+
+        it('should reject a negative input length cap and accept 0 as no limit', async () => {
+            const negative = await settingsManager.updateEmbeddingRequest({ embeddingMaxInputChars: -1 });
+            expect(negative.success).toBe(false);
+            expect(negative.errors?.[0].field).toBe('embeddingMaxInputChars');
+            expect(mockSettings.embeddingMaxInputChars).toBe(8000);
+
+            const unlimited = await settingsManager.updateEmbeddingRequest({ embeddingMaxInputChars: 0 });
+            expect(unlimited.success).toBe(true);
+            expect(mockServiceUpdaters.embeddingRequest).toHaveBeenCalledWith({ embeddingMaxInputChars: 0 });
+        });
+    });
+
     describe('LLM Settings', () => {
         it('should update and initialize LLM config', async () => {
             const result = await settingsManager.updateLlm({
@@ -208,6 +280,91 @@ describe('SettingsManager', () => {
         });
     });
 
+    describe('Qdrant Tuning Settings', () => {
+        it('should update qdrant tuning config', async () => {
+            const result = await settingsManager.updateQdrantTuning({
+                hnswM: 32,
+                hnswEfConstruct: 200,
+                onDiskPayload: false,
+            });
+
+            expect(result.success).toBe(true);
+            expect(mockSettings.qdrantTuning.hnswM).toBe(32);
+            expect(mockSettings.qdrantTuning.hnswEfConstruct).toBe(200);
+            expect(mockSaveSettings).toHaveBeenCalled();
+            expect(mockServiceUpdaters.qdrantTuning).toHaveBeenCalled();
+        });
+
+        it('should validate hnswM range', async () => {
+            const result = await settingsManager.updateQdrantTuning({
+                hnswM: 128, // 超出范围 4-64
+            });
+
+            expect(result.success).toBe(false);
+            expect(result.errors?.[0].field).toBe('hnswM');
+        });
+
+        it('should validate hnswEfConstruct range', async () => {
+            const result = await settingsManager.updateQdrantTuning({
+                hnswEfConstruct: 2,
+            });
+
+            expect(result.success).toBe(false);
+            expect(result.errors?.[0].field).toBe('hnswEfConstruct');
+        });
+    });
+
+    describe('Indexing Settings', () => {
+        it('should update indexing config', async () => {
+            const result = await settingsManager.updateIndexing({
+                embedWithHeaderPath: true,
+            });
+
+            expect(result.success).toBe(true);
+            expect(mockSettings.indexing.embedWithHeaderPath).toBe(true);
+            expect(mockServiceUpdaters.indexing).toHaveBeenCalled();
+        });
+
+        it('should reject a negative index timeout', async () => {
+            const result = await settingsManager.updateIndexing({
+                indexTimeoutMs: -1,
+            });
+
+            expect(result.success).toBe(false);
+            expect(result.errors?.[0].field).toBe('indexTimeoutMs');
+        });
+    });
+
+    describe('Search Settings', () => {
+        it('should update the score transform', async () => {
+            const result = await settingsManager.updateSearch({ scoreTransform: 'minmax' });
+
+            expect(result.success).toBe(true);
+            expect(mockSettings.search.scoreTransform).toBe('minmax');
+            expect(mockServiceUpdaters.search).toHaveBeenCalled();
+        });
+
+        it('should save a fitted calibration with the search settings', async () => {
+            let saved: unknown;
+            mockSaveSettings.mockImplementation(async () => {
+                saved = mockSettings.search.calibration;
+            });
+
+            const result = await settingsManager.updateSearch({ calibration: { slope: 40, intercept: -1.2 } });
+
+            expect(result.success).toBe(true);
+            expect(saved).toEqual({ slope: 40, intercept: -1.2 });
+            expect(mockServiceUpdaters.search).toHaveBeenCalledWith({ calibration: { slope: 40, intercept: -1.2 } });
+        });
+
+        it('should reject an unknown score transform', async () => {
+            const result = await settingsManager.updateSearch({ scoreTransform: 'log' as never });
+
+            expect(result.success).toBe(false);
+            expect(mockServiceUpdaters.search).not.toHaveBeenCalled();
+        });
+    });
+
     describe('Get Settings', () => {
         it('should return a readonly snapshot of settings', () => {
             const settings = settingsManager.getSettings();
@@ -11,9 +11,22 @@ import type {
     ServiceUpdaters,
 } from './types';
 import type { BaseModelConfig } from '../types/setting';
+import type { EmbeddingRequestConfig } from '../types/setting';
 import type { ConceptExtractionConfig } from '../types/setting';
 import type { ConceptFEConfig } from '../types/setting';
 import type { ConceptSkipConfig } from '../types/setting';
+import type { QdrantTuningConfig } from '../types/setting';
+import type { IndexingConfig } from '../types/setting';
+import type { SearchConfig } from '../types/setting';
+
+// Smaller chunks carry too little context to embed meaningfully
+const MIN_CHUNK_SIZE = 100;
+
+// Ollama keep_alive: a Go duration such as 30m or 1h30m, or a number of seconds (negative keeps forever)
+const KEEP_ALIVE_PATTERN = /^-?(\d+(\.\d+)?(ns|us|µs|ms|s|m|h))+$|^-?\d+$/;
+
+// RFC 7230 token characters allowed in a header name
+const HEADER_NAME_PATTERN = /^[!#$%&'*+.^_`|~0-9A-Za-z-]+$/;
 
 /**
  * Embedding Settings Handler
@@ -54,6 +67,57 @@ export class EmbeddingSettingsHandler implements SettingsGroupHandler<BaseModelC
     }
 }
 
+/**
+ * Embedding Request Settings Handler
+ * Handles options sent with each embedding request (Ollama keep_alive, headers, input length cap)
+ */
+export class EmbeddingRequestSettingsHandler implements SettingsGroupHandler<EmbeddingRequestConfig> {
+    readonly groupName = 'embeddingRequest';
+
+    constructor(private updateService: (config: Partial<EmbeddingRequestConfig>) => void | Promise<void>) { }
+
+    validate(config: Partial<EmbeddingRequestConfig>): SettingsUpdateResult {
+        if (config.embeddingKeepAlive !== undefined && !KEEP_ALIVE_PATTERN.test(config.embeddingKeepAlive)) {
+            return {
+                success: false,
+                errors: [{ field: 'embeddingKeepAlive', message: 'Must be a duration like 30m or 1h, or a number of seconds' }],
+            };
+        }
+
+        if (config.embeddingHeaders !== undefined) {
+            for (const [name, value] of Object.entries(config.embeddingHeaders)) {
+                if (!HEADER_NAME_PATTERN.test(name)) {
+                    return {
+                        success: false,
+                        errors: [{ field: 'embeddingHeaders', message: `Invalid header name: ${name}` }],
+                    };
+                }
+                if (/[\r\n]/.test(value)) {
+                    return {
+                        success: false,
+                        errors: [{ field: 'embeddingHeaders', message: `Header ${name} must not contain line breaks` }],
+                    };
+                }
+            }
+        }
+
+        if (config.embeddingMaxInputChars !== undefined &&
+            (!Number.isInteger(config.embeddingMaxInputChars) || config.embeddingMaxInputChars < 0)) {
+            return {
+                success: false,
+                errors: [{ field: 'embeddingMaxInputChars', message: 'Must be a non-negative integer (0 = no limit)' }],
+            };
+        }
+
+        return { success: true };
+    }
+
+    async apply(config: Partial<EmbeddingRequestConfig>, context: SettingsContext): Promise<void> {
+        await this.updateService(config);
+        await context.saveSettings();
+    }
+}
+
 /**
  * LLM Settings Handler
  * Handles updates for LLM configuration (used by metadata extractor)
@@ -175,3 +239,214 @@ export class ConceptSkipSettingsHandler implements SettingsGroupHandler<ConceptS
     }
 }
 
+/**
+ * Qdrant Tuning Settings Handler
+ * Handles HNSW/payload storage parameters applied at collection creation
+ */
+export class QdrantTuningSettingsHandler implements SettingsGroupHandler<QdrantTuningConfig> {
+    readonly groupName = 'qdrantTuning';
+
+    constructor(private updateService: (config: Partial<QdrantTuningConfig>) => void | Promise<void>) { }
+
+    validate(config: Partial<QdrantTuningConfig>): SettingsUpdateResult {
+        if (config.hnswM !== undefined) {
+            if (!Number.isInteger(config.hnswM) || config.hnswM < 4 || config.hnswM > 64) {
+                return {
+                    success: false,
+                    errors: [{ field: 'hnswM', message: 'Must be an integer between 4 and 64' }],
+                };
+            }
+        }
+
+        if (config.hnswEfConstruct !== undefined) {
+            if (!Number.isInteger(config.hnswEfConstruct) || config.hnswEfConstruct < 4 || config.hnswEfConstruct > 1000) {
+                return {
+                    success: false,
+                    errors: [{ field: 'hnswEfConstruct', message: 'Must be an integer between 4 and 1000' }],
+                };
+            }
+        }
+
+        if (config.indexingThreshold !== undefined) {
+            if (!Number.isInteger(config.indexingThreshold) || config.indexingThreshold < 0) {
+                return {
+                    success: false,
+                    errors: [{ field: 'indexingThreshold', message: 'Must be a non-negative integer' }],
+                };
+            }
+        }
+
+        if (config.quantizationQuantile !== undefined) {
+            const quantile = config.quantizationQuantile;
+            if (!Number.isFinite(quantile) || quantile < 0.5 || quantile > 1) {
+                return {
+                    success: false,
+                    errors: [{ field: 'quantizationQuantile', message: 'Must be between 0.5 and 1' }],
+                };
+            }
+        }
+
+        return { success: true };
+    }
+
+    async apply(config: Partial<QdrantTuningConfig>, context: SettingsContext): Promise<void> {
+        await this.updateService(config);
+        await context.saveSettings();
+    }
+}
+
+/**
+ * Indexing Settings Handler
+ * Handles how chunks are turned into embedding input
+ */
+export class IndexingSettingsHandler implements SettingsGroupHandler<IndexingConfig> {
+    readonly groupName = 'indexing';
+
+    constructor(private updateService: (config: Partial<IndexingConfig>) => void | Promise<void>) { }
+
+    validate(config: Partial<IndexingConfig>): SettingsUpdateResult {
+        if (config.indexTimeoutMs !== undefined) {
+            if (!Number.isInteger(config.indexTimeoutMs) || config.indexTimeoutMs < 0) {
+                return {
+                    success: false,
+                    errors: [{ field: 'indexTimeoutMs', message: 'Must be a non-negative integer' }],
+                };
+            }
+        }
+
+        if (config.maxChunksPerDoc !== undefined) {
+            if (!Number.isInteger(config.maxChunksPerDoc) || config.maxChunksPerDoc < 0) {
+                return {
+                    success: false,
+                    errors: [{ field: 'maxChunksPerDoc', message: 'Must be a non-negative integer' }],
+                };
+            }
+        }
+
+        if (config.maxChunkSize !== undefined) {
+            if (!Number.isInteger(config.maxChunkSize) || config.maxChunkSize < MIN_CHUNK_SIZE) {
+                return {
+                    success: false,
+                    errors: [{ field: 'maxChunkSize', message: `Must be an integer of at least ${MIN_CHUNK_SIZE}` }],
+                };
+            }
+        }
+
+        if (config.chunkStrategy !== undefined && !['header', 'paragraph', 'fixed'].includes(config.chunkStrategy)) {
+            return {
+                success: false,
+                errors: [{ field: 'chunkStrategy', message: 'Must be one of: header, paragraph, fixed' }],
+            };
+        }
+
+        if (config.emptySections !== undefined && !['keep', 'merge', 'skip'].includes(config.emptySections)) {
+            return {
+                success: false,
+                errors: [{ field: 'emptySections', message: 'Must be one of: keep, merge, skip' }],
+            };
+        }
+
+        if (config.oversizedPolicy !== undefined && !['split', 'skip', 'placeholder'].includes(config.oversizedPolicy)) {
+            return {
+                success: false,
+                errors: [{ field: 'oversizedPolicy', message: 'Must be one of: split, skip, placeholder' }],
+            };
+        }
+
+        for (const field of ['indexConcurrency', 'persistBatchSize'] as const) {
+            const value = config[field];
+            if (value !== undefined && (!Number.isInteger(value) || value < 1)) {
+                return {
+                    success: false,
+                    errors: [{ field, message: 'Must be a positive integer' }],
+                };
+            }
+        }
+
+        return { success: true };
+    }
+
+    async apply(config: Partial<IndexingConfig>, context: SettingsContext): Promise<void> {
+        await this.updateService(config);
+        await context.saveSettings();
+    }
+}
+
+/**
+ * Search Settings Handler
+ * Handles how search results are presented
+ */
+export class SearchSettingsHandler implements SettingsGroupHandler<SearchConfig> {
+    readonly groupName = 'search';
+
+    constructor(private updateService: (config: Partial<SearchConfig>) => void | Promise<void>) { }
+
+    validate(config: Partial<SearchConfig>): SettingsUpdateResult {
+        if (config.scoreTransform !== undefined && !['raw', 'minmax', 'rank'].includes(config.scoreTransform)) {
+            return {
+                success: false,
+                errors: [{ field: 'scoreTransform', message: 'Must be one of: raw, minmax, rank' }],
+            };
+        }
+
+        if (config.recencyBoost !== undefined) {
+            if (!Number.isFinite(config.recencyBoost) || config.recencyBoost < 0 || config.recencyBoost > 1) {
+                return {
+                    success: false,
+                    errors: [{ field: 'recencyBoost', message: 'Must be between 0 and 1' }],
+                };
+            }
+        }
+
+        if (config.maxResults !== undefined) {
+            if (!Number.isInteger(config.maxResults) || config.maxResults < 1) {
+                return {
+                    success: false,
+                    errors: [{ field: 'maxResults', message: 'Must be a positive integer' }],
+                };
+            }
+        }
+
+        if (config.minQueryTokens !== undefined) {
+            if (!Number.isInteger(config.minQueryTokens) || config.minQueryTokens < 0) {
+                return {
+                    success: false,
+                    errors: [{ field: 'minQueryTokens', message: 'Must be a non-negative integer' }],
+                };
+            }
+        }
+
+        if (config.weakQueryBehavior !== undefined && !['empty', 'widen'].includes(config.weakQueryBehavior)) {
+            return {
+                success: false,
+                errors: [{ field: 'weakQueryBehavior', message: 'Must be one of: empty, widen' }],
+            };
+        }
+
+        if (config.calibration !== undefined) {
+            const { slope, intercept } = config.calibration;
+            if (!Number.isFinite(slope) || slope <= 0 || !Number.isFinite(intercept)) {
+                return {
+                    success: false,
+                    errors: [{ field: 'calibration', message: 'Slope must be a positive number and intercept finite' }],
+                };
+            }
+        }
+
+        if (config.recencyHalfLifeDays !== undefined) {
+            if (!Number.isFinite(config.recencyHalfLifeDays) || config.recencyHalfLifeDays <= 0) {
+                return {
+                    success: false,
+                    errors: [{ field: 'recencyHalfLifeDays', message: 'Must be a positive number' }],
+                };
+            }
+        }
+
+        return { success: true };
+    }
+
+    async apply(config: Partial<SearchConfig>, context: SettingsContext): Promise<void> {
+        await this.updateService(config);
+        await context.saveSettings();
+    }
+}
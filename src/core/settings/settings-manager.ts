@@ -6,14 +6,22 @@
 import type { MemoEchoSettings } from '../../views/settings';
 import type { SettingsUpdateResult, SettingsContext, ServiceUpdaters } from './types';
 import { EmbeddingSettingsHandler } from './settings-handlers';
+import { EmbeddingRequestSettingsHandler } from './settings-handlers';
 import { LlmSettingsHandler } from './settings-handlers';
 import { ConceptExtractionSettingsHandler } from './settings-handlers';
 import { ConceptFESettingsHandler } from './settings-handlers';
 import { ConceptSkipSettingsHandler } from './settings-handlers';
+import { QdrantTuningSettingsHandler } from './settings-handlers';
+import { IndexingSettingsHandler } from './settings-handlers';
+import { SearchSettingsHandler } from './settings-handlers';
 import type { BaseModelConfig } from '../types/setting';
+import type { EmbeddingRequestConfig } from '../types/setting';
 import type { ConceptExtractionConfig } from '../types/setting';
 import type { ConceptFEConfig } from '../types/setting';
 import type { ConceptSkipConfig } from '../types/setting';
+import type { QdrantTuningConfig } from '../types/setting';
+import type { IndexingConfig } from '../types/setting';
+import type { SearchConfig } from '../types/setting';
 
 /**
  * SettingsManager - Encapsulates all settings update logic
@@ -49,10 +57,14 @@ export class SettingsManager {
 
         // Initialize handlers
         this.handlers.set('embedding', new EmbeddingSettingsHandler(serviceUpdaters.embedding));
+        this.handlers.set('embeddingRequest', new EmbeddingRequestSettingsHandler(serviceUpdaters.embeddingRequest));
         this.handlers.set('llm', new LlmSettingsHandler(serviceUpdaters.llm));
         this.handlers.set('conceptExtraction', new ConceptExtractionSettingsHandler(serviceUpdaters.conceptExtraction));
         this.handlers.set('conceptFE', new ConceptFESettingsHandler(serviceUpdaters.conceptFE));
         this.handlers.set('conceptSkip', new ConceptSkipSettingsHandler(serviceUpdaters.conceptSkip));
+        this.handlers.set('qdrantTuning', new QdrantTuningSettingsHandler(serviceUpdaters.qdrantTuning));
+        this.handlers.set('indexing', new IndexingSettingsHandler(serviceUpdaters.indexing));
+        this.handlers.set('search', new SearchSettingsHandler(serviceUpdaters.search));
     }
 
     /**
@@ -64,6 +76,13 @@ export class SettingsManager {
         return this.updateGroup('embedding', this.settings.embeddingConfig, config);
     }
 
+    /**
+     * Update embedding request options (stored as top-level settings)
+     */
+    async updateEmbeddingRequest(config: Partial<EmbeddingRequestConfig>): Promise<SettingsUpdateResult> {
+        return this.updateGroup<EmbeddingRequestConfig>('embeddingRequest', this.settings, config);
+    }
+
     /**
      * Update LLM configuration
      */
@@ -92,6 +111,27 @@ export class SettingsManager {
         return this.updateGroup('conceptSkip', this.settings.conceptSkip, config);
     }
 
+    /**
+     * Update Qdrant collection tuning (applies to newly created collections)
+     */
+    async updateQdrantTuning(config: Partial<QdrantTuningConfig>): Promise<SettingsUpdateResult> {
+        return this.updateGroup('qdrantTuning', this.settings.qdrantTuning, config);
+    }
+
+    /**
+     * Update indexing configuration
+     */
+    async updateIndexing(config: Partial<IndexingConfig>): Promise<SettingsUpdateResult> {
+        return this.updateGroup('indexing', this.settings.indexing, config);
+    }
+
+    /**
+     * Update search result configuration
+     */
+    async updateSearch(config: Partial<SearchConfig>): Promise<SettingsUpdateResult> {
+        return this.updateGroup('search', this.settings.search, config);
+    }
+
     /**
      * Generic group update method
      */
@@ -117,14 +157,20 @@ export class SettingsManager {
             saveSettings: this.saveSettings,
         };
 
+        // Merge updates into settings object first, so the handler's save writes them
+        const previous: Partial<T> = {};
+        for (const key of Object.keys(updates) as Array<keyof T>) {
+            previous[key] = currentConfig[key];
+        }
+        Object.assign(currentConfig, updates);
+
         // Apply updates (handler will call service updater and save)
         try {
             await handler.apply(updates, context);
-            // Merge updates into settings object (for object configs)
-            Object.assign(currentConfig, updates);
             console.log(`[SettingsManager] Updated ${groupName}`, updates);
             return { success: true };
         } catch (error) {
+            Object.assign(currentConfig, previous);
             console.error(`[SettingsManager] Failed to apply settings for ${groupName}`, error);
             return {
                 success: false,
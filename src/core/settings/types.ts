@@ -4,9 +4,13 @@
  */
 
 import type { BaseModelConfig } from '../types/setting';
+import type { EmbeddingRequestConfig } from '../types/setting';
 import type { ConceptExtractionConfig } from '../types/setting';
 import type { ConceptFEConfig } from '../types/setting';
 import type { ConceptSkipConfig } from '../types/setting';
+import type { QdrantTuningConfig } from '../types/setting';
+import type { IndexingConfig } from '../types/setting';
+import type { SearchConfig } from '../types/setting';
 import type { EmbeddingConfig } from '../types/embedding';
 import type { MetadataExtractorConfig } from '../types/extraction';
 
@@ -42,9 +46,13 @@ export interface SettingsGroupHandler<TConfig> {
  */
 export interface ServiceUpdaters {
     embedding: (config: Partial<BaseModelConfig>) => void | Promise<void>;
+    embeddingRequest: (config: Partial<EmbeddingRequestConfig>) => void | Promise<void>;
     llm: (config: Partial<BaseModelConfig>) => void | Promise<void>;
     conceptExtraction: (config: Partial<ConceptExtractionConfig>) => void | Promise<void>;
     conceptExtractionSettings: () => void | Promise<void>;
     conceptFE: (config: Partial<ConceptFEConfig>) => void | Promise<void>;
     conceptSkip: (config: Partial<ConceptSkipConfig>) => void | Promise<void>;
+    qdrantTuning: (config: Partial<QdrantTuningConfig>) => void | Promise<void>;
+    indexing: (config: Partial<IndexingConfig>) => void | Promise<void>;
+    search: (config: Partial<SearchConfig>) => void | Promise<void>;
 }
@@ -15,8 +15,27 @@ export interface EmbeddingConfig {
     openaiApiKey?: string;
     openaiModel?: string;
     dimension?: number;  // Vector dimension for the model
+    maxConcurrency?: number;  // Max in-flight embedding requests across all callers
+    ollamaKeepAlive?: string;  // How long Ollama keeps the model loaded, e.g. "30m"; "0" unloads immediately
+    ollamaHeaders?: Record<string, string>;  // Sent with every Ollama request, e.g. Authorization for an auth proxy
+    maxInputChars?: number;  // Longer inputs are truncated before embedding, 0 disables
 }
 
+/**
+ * Default Ollama keep_alive, long enough to avoid reloads between searches
+ */
+export const DEFAULT_OLLAMA_KEEP_ALIVE = '30m';
+
+/**
+ * Default embedding input limit in characters, well inside common 8k-token model windows
+ */
+export const DEFAULT_EMBEDDING_MAX_INPUT_CHARS = 8000;
+
+/**
+ * Default limit for concurrent embedding requests
+ */
+export const DEFAULT_EMBEDDING_CONCURRENCY = 4;
+
 /**
  * Default dimensions for common embedding models
  */
@@ -38,6 +57,7 @@ export const MODEL_DIMENSIONS: Record<string, number> = {
  * Result of batch embedding operation
  */
 export interface BatchEmbeddingResult {
-    successful: number[][];
+    vectors: Array<number[] | null>; // Aligned with the input texts, null where embedding failed
+    successful: number[][]; // Successful vectors in input order
     failed: Array<{ index: number; error: Error }>;
 }
@@ -27,6 +27,55 @@ export interface ChunkResult {
 	start_line: number; // 1-indexed line number where chunk starts
 	end_line: number; // 1-indexed line number where chunk ends
 	header_path: string; // Formatted header hierarchy (e.g., "# H1 > ## H2")
+	oversized?: boolean; // A whitespace-free line longer than maxChunkSize, kept whole for oversizedPolicy to handle
+}
+
+/**
+ * How a document is split into chunks
+ * header: by Markdown headings (default), paragraph: by blank lines, fixed: by length only
+ */
+export type ChunkStrategyName = "header" | "paragraph" | "fixed";
+
+/**
+ * What happens to a heading with no body before the next heading
+ * keep: chunk of just the heading line (default), merge: prepend it to the next non-empty section
+ * (dropped when none follows), skip: drop it
+ */
+export type EmptySectionBehavior = "keep" | "merge" | "skip";
+
+/**
+ * What happens to a line with no whitespace longer than maxChunkSize (e.g. a base64 blob)
+ * split: cut into fixed-size fragments (default), skip: not indexed, placeholder: indexed as "[omitted binary blob N chars]"
+ */
+export type OversizedPolicy = "split" | "skip" | "placeholder";
+
+/**
+ * A chunking strategy; chunk content joined in order reproduces the (newline-normalized) input
+ */
+export interface ChunkStrategy {
+	chunk(content: string): ChunkResult[];
+}
+
+/**
+ * Options controlling how Markdown sections become chunk content
+ */
+export interface ChunkerOptions {
+	maxChunkSize?: number; // Target upper bound on chunk length in characters
+	includeHeadingInContent?: boolean; // Keep the heading line at the top of each section's chunk (default true)
+	splitOnHr?: boolean; // Start a new chunk after `---`/`***`/`___` rules within a section (default false)
+	emptySections?: EmptySectionBehavior; // Headings with no body, only applies with includeHeadingInContent (default "keep")
+	oversizedPolicy?: OversizedPolicy; // Anything but "split" keeps unbreakable lines whole as `oversized` chunks (default "split")
+}
+
+/**
+ * Chunk size distribution for a document, for tuning chunking without indexing
+ */
+export interface ChunkReport {
+	count: number;
+	minLen: number;
+	maxLen: number;
+	avgLen: number;
+	histogram: Array<{ upTo: number; count: number }>; // Buckets of chunk length, ascending
 }
 
 export interface SemanticChunk {
@@ -41,6 +90,88 @@ export interface UnifiedIndexResult {
 	concepts: ExtractedConceptDetail[];
 }
 
+/**
+ * Progress of indexing a single file
+ */
+export interface IndexProgress {
+	embedded: number;
+	total: number;
+	capped: boolean; // More chunks than maxChunksPerDoc; the rest were merged or dropped
+	skipped: number; // Chunks with no text left to embed after preprocessing, not stored
+	oversized: number; // Unbreakable chunks skipped or stored as a placeholder, see oversizedPolicy
+}
+
+/**
+ * A file to index as part of a batch
+ */
+export interface IndexFileInput {
+	filePath: string;
+	content: string;
+	strategy?: ChunkStrategyName; // Overrides the configured chunk strategy for this file
+}
+
+/**
+ * Per-file outcome of a batch index
+ */
+export interface IndexFileReport {
+	filePath: string;
+	success: boolean;
+	chunks: number;
+	capped?: boolean;
+	skipped?: number;
+	oversized?: number;
+	unchanged?: boolean; // Content hash matched the stored one, nothing was re-embedded
+	error?: string;
+}
+
+/**
+ * Result of indexing several files; failures are reported, not thrown
+ */
+export interface BatchIndexReport {
+	files: IndexFileReport[];
+	indexed: number;
+	unchanged: number; // Of `indexed`, files skipped because their content hash matched
+	failed: number;
+	totalChunks: number;
+}
+
+/**
+ * Progress of a vault sync, reported after each file
+ */
+export interface BatchIndexProgress {
+	done: number;
+	total: number;
+	failed: number;
+}
+
+export type IndexJobState =
+	| "queued"
+	| "running"
+	| "completed"
+	| "failed"
+	| "cancelled";
+
+/**
+ * A background indexing job, polled by id
+ */
+export interface IndexJob {
+	id: string;
+	state: IndexJobState;
+	processed: number; // Files done so far, successful or not
+	total: number;
+	errors: Array<{ filePath: string; error: string }>; // Per-file failures; a failed job has one entry with an empty path
+}
+
+/**
+ * What the job queue finished and what it abandoned when shut down
+ */
+export interface IndexShutdownSummary {
+	completed: number; // Jobs that ran to the end, including ones finishing during shutdown
+	failed: number;
+	cancelled: number; // Queued jobs dropped and running jobs stopped early
+	abandonedFiles: number; // Files of cancelled jobs that were never indexed
+}
+
 /**
  * Chunk cached in memory
  */
@@ -71,6 +202,7 @@ export interface MultiVectorQueuedChunk {
 		[VECTOR_NAMES.SUMMARY]: number[];
 		[VECTOR_NAMES.TITLE]: number[];
 	};
+	subVectors?: number[][]; // Per-paragraph content vectors, only with `multiVector`
 	metadata: Record<string, any>;
 }
 
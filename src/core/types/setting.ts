@@ -1,3 +1,11 @@
+import type { PointType } from "./vector";
+import type {
+	ChunkStrategyName,
+	EmptySectionBehavior,
+	OversizedPolicy,
+} from "./indexing";
+import { DEFAULT_STOP_WORDS } from "@core/constants";
+
 export interface BaseModelConfig {
 	provider: "ollama" | "openai";
 	model: string;
@@ -12,6 +20,15 @@ export const DEFAULT_EMBEDDING_CONFIG: BaseModelConfig = {
 	apiKey: "",
 };
 
+/**
+ * Options sent with each embedding request, kept as top-level settings
+ */
+export interface EmbeddingRequestConfig {
+	embeddingKeepAlive: string; // Ollama keep_alive, e.g. "30m" or "1h"; "0" unloads right after each request
+	embeddingHeaders: Record<string, string>; // Extra HTTP headers for Ollama requests (auth proxies)
+	embeddingMaxInputChars: number; // Truncate longer inputs before embedding; 0 = no limit
+}
+
 export const DEFAULT_LLM_CONFIG: BaseModelConfig = {
 	provider: "ollama",
 	model: "qwen3:4b",
@@ -58,6 +75,114 @@ export const DEFAULT_CONCEPT_SKIP_CONFIG: ConceptSkipConfig = {
 	minTextLength: 100,
 };
 
+// Where vectors are stored: a Qdrant server, or in memory for tests and small vaults (lost on reload)
+export type VectorBackendKind = "qdrant" | "memory";
+
+export interface QdrantTuningConfig {
+	hnswM: number; // HNSW graph degree, 4-64
+	hnswEfConstruct: number; // HNSW build-time search depth, 4-1000
+	onDiskPayload: boolean; // Store payload on disk instead of RAM
+	indexingThreshold: number; // KB of vectors per segment before Qdrant builds its HNSW index
+	optimizeAfterBulkWrites: boolean; // Ask Qdrant to optimize after an import or re-embedding
+	// int8 scalar quantization: about 4x less vector memory, searches rescore with the originals so recall drops only slightly
+	scalarQuantization: boolean;
+	quantizationQuantile: number; // Share of values kept inside the int8 range, outliers are clamped, 0.5-1
+	quantizationAlwaysRam: boolean; // Keep quantized vectors in RAM even when originals are on disk
+}
+
+// Mirrors Qdrant's own defaults, only applied when a collection is created
+export const DEFAULT_QDRANT_TUNING_CONFIG: QdrantTuningConfig = {
+	hnswM: 16,
+	hnswEfConstruct: 100,
+	onDiskPayload: true,
+	indexingThreshold: 10000,
+	optimizeAfterBulkWrites: true,
+	scalarQuantization: false,
+	quantizationQuantile: 0.99,
+	quantizationAlwaysRam: true,
+};
+
+export type ChunkOverflowPolicy = "merge" | "truncate";
+
+export interface IndexingConfig {
+	embedWithHeaderPath: boolean; // Prepend header_path to the text sent to the embedder
+	embedCalloutTitles: boolean; // Prepend each callout's type and title to the text sent to the embedder
+	stripMarkdown: boolean; // Remove emphasis, link URLs and comments from the embedder input
+	plainTextExtensions: string[]; // Indexed as plain text (no header parsing), e.g. ["txt"]
+	caseInsensitivePaths: boolean; // Match delete/rename paths ignoring case (uses filePathKey)
+	indexTimeoutMs: number; // Abort indexing a single file after this long, 0 disables
+	indexHeadings: boolean; // Also index each heading's text as its own small point
+	multiVector: boolean; // Also store one vector per paragraph and match on the best one (max-sim); needs a collection created with it
+	skipUnchangedFiles: boolean; // Sync skips files whose content and chunk settings match the stored contentHash
+	maxChunksPerDoc: number; // Cap on chunks embedded per file, 0 disables
+	chunkOverflowPolicy: ChunkOverflowPolicy; // What happens to chunks past the cap
+	maxChunkSize: number; // Target chunk length in characters for rule-based chunking
+	chunkStrategy: ChunkStrategyName; // How Markdown notes are chunked; plain-text files use "fixed"
+	includeHeadingInContent: boolean; // Keep the heading line in chunk content; off embeds body text only
+	splitOnHr: boolean; // Treat ---/***/___ rules as chunk boundaries within a section
+	emptySections: EmptySectionBehavior; // Headings with no body: own chunk, merged into the next section, or dropped
+	oversizedPolicy: OversizedPolicy; // Whitespace-free lines longer than maxChunkSize: fragments, dropped, or a placeholder
+	indexConcurrency: number; // Files indexed at once by a vault sync
+	persistBatchSize: number; // Queued chunks that trigger a flush mid-file; each file is flushed once indexed
+}
+
+export const DEFAULT_INDEXING_CONFIG: IndexingConfig = {
+	embedWithHeaderPath: false,
+	embedCalloutTitles: false,
+	stripMarkdown: false,
+	plainTextExtensions: ["txt"],
+	caseInsensitivePaths: false,
+	indexTimeoutMs: 0,
+	indexHeadings: false,
+	multiVector: false,
+	skipUnchangedFiles: true,
+	maxChunksPerDoc: 0,
+	chunkOverflowPolicy: "merge",
+	maxChunkSize: 500,
+	chunkStrategy: "header",
+	includeHeadingInContent: true,
+	splitOnHr: false,
+	emptySections: "keep",
+	oversizedPolicy: "split",
+	indexConcurrency: 4,
+	persistBatchSize: 50,
+};
+
+export type ScoreTransform = "raw" | "minmax" | "rank";
+
+export type WeakQueryBehavior = "empty" | "widen";
+
+/**
+ * Logistic mapping from raw score to relevance probability: 1 / (1 + e^-(slope * score + intercept))
+ * Raw scores are RRF fusion scores, not cosine similarities; fitted per embedding model, see SearchService.calibrate
+ */
+export interface ScoreCalibration {
+	slope: number; // > 0, so higher scores always map higher
+	intercept: number;
+}
+
+export interface SearchConfig {
+	scoreTransform: ScoreTransform; // How result scores are spread for display; rawScore is always kept
+	recencyBoost: number; // Score bonus (0–1) for just-indexed chunks, added to scores relative to the best match; 0 disables
+	recencyHalfLifeDays: number; // Age at which the recency bonus is halved
+	maxResults: number; // Upper bound on any search's limit
+	stopWords: string[]; // Words ignored when counting a query's significant tokens
+	minQueryTokens: number; // Queries with fewer significant tokens are weak, 0 disables the check
+	weakQueryBehavior: WeakQueryBehavior; // Return nothing, or search a wider limit and flag results
+	defaultPointType?: PointType; // Point type searched when a query sets none, e.g. for a chunk-only collection
+	calibration?: ScoreCalibration; // Applied to queries that request `calibrated` scores
+}
+
+export const DEFAULT_SEARCH_CONFIG: SearchConfig = {
+	scoreTransform: "raw",
+	recencyBoost: 0,
+	recencyHalfLifeDays: 30,
+	maxResults: 100,
+	stopWords: DEFAULT_STOP_WORDS,
+	minQueryTokens: 0,
+	weakQueryBehavior: "empty",
+};
+
 /**
  * Union type of all settings config types for type-safe updates
  * Used by SettingsManager to ensure type safety across all config types
@@ -65,6 +190,10 @@ export const DEFAULT_CONCEPT_SKIP_CONFIG: ConceptSkipConfig = {
  */
 export type SettingsConfigType =
 	| BaseModelConfig
+	| EmbeddingRequestConfig
 	| ConceptExtractionConfig
 	| ConceptFEConfig
-	| ConceptSkipConfig;
+	| ConceptSkipConfig
+	| QdrantTuningConfig
+	| IndexingConfig
+	| SearchConfig;
@@ -12,9 +12,96 @@ import type { VECTOR_NAMES } from '@core/constants';
 export interface MultiVectorItem {
     id: string;
     vectors: Record<VECTOR_NAMES, number[]>;
+    subVectors?: number[][]; // One content vector per paragraph, stored as a max-sim multivector when enabled
     metadata: Record<string, any>;
 }
 
+/**
+ * Point type stored in the `type` payload field
+ */
+export type PointType = 'chunk' | 'concept';
+
+/**
+ * Indexed state of a single file
+ */
+export interface PathInfo {
+    chunks: number;
+    latestIndexedAt: number | null;
+    contentHash: string | null;
+}
+
+/**
+ * One line of a JSONL export: flat fields for analysis, full payload for restore
+ */
+export interface ExportedPoint {
+    id: string;
+    pointId?: string | number; // Backend's own point id, when it differs from `id`
+    filePath: string | null;
+    content: string | null;
+    type: string | null;
+    indexedAt: number | null;
+    metadata: Record<string, any>;
+    vectors?: Record<string, number[] | number[][]>; // Only with includeVectors; the multivector is a list of vectors
+}
+
+/**
+ * A point parsed back from an export line, with every named vector it was exported with
+ */
+export interface ImportedPoint {
+    id: string;
+    pointId?: string | number;
+    vectors: Record<string, number[]>; // Chunk or concept vectors, all of one dimension
+    subVectors?: number[][]; // The content_multi_vec multivector, if the point had one
+    metadata: Record<string, any>;
+}
+
+/**
+ * Narrows a file delete to the points of one index run (`indexRunId` payload), or to all but that run's
+ */
+export interface IndexRunScope {
+    indexRunId?: string;
+    exceptIndexRunId?: string;
+}
+
+/**
+ * Outcome of importing an export; bad lines are skipped, not thrown
+ */
+export interface ImportReport {
+    imported: number;
+    skipped: number;
+}
+
+/**
+ * Outcome of re-embedding stored points; failed points keep their old vectors
+ */
+export interface ReembedReport {
+    updated: number;
+    failed: number;
+}
+
+/**
+ * Keyword payload fields with facet counts
+ */
+export type FacetField = 'tags' | 'folders';
+
+/**
+ * One value of a facet field and the number of points carrying it
+ */
+export interface FacetCount {
+    value: string;
+    count: number;
+}
+
+/**
+ * Backend connectivity and collection state
+ */
+export interface BackendHealth {
+    connected: boolean;
+    collectionName: string;
+    dimension: number | null; // Stored vector dimension, null if the collection doesn't exist yet
+    pointsCount: number | null; // null if Qdrant reported no count and the exact count failed
+}
+
 /**
  * Search result from vector backend
  */
@@ -22,6 +109,21 @@ export interface SearchResult {
     id: string;
     score: number;
     metadata: Record<string, any>;
+    vector?: number[]; // Stored content vector, only when requested via withVectors
+}
+
+/**
+ * Payload filter criteria, all given criteria must hold
+ */
+export interface SearchFilter {
+    tags?: string[]; // Points carrying any of these tags
+    since?: number; // indexedAt lower bound (ms since epoch, inclusive)
+    until?: number; // indexedAt upper bound (ms since epoch, inclusive)
+    folder?: string; // Only points in this folder or its subfolders (matches `folders`)
+    type?: PointType; // Only points with this `type` payload
+    excludeFilePath?: string; // Drop points of this file
+    minDepth?: number; // Heading depth bounds (inclusive), 0 = before any heading; points without `depth` never match
+    maxDepth?: number;
 }
 
 /**
@@ -34,9 +136,8 @@ export interface SearchOptions {
         summary?: number;
         title?: number;
     };
-    filter?: {
-        tags?: string[];
-    };
+    filter?: SearchFilter;
+    withVectors?: boolean; // Return stored content vectors (off by default, large payload)
 }
 
 /**
@@ -49,6 +150,16 @@ export interface VectorBackend {
      */
     initialize(): Promise<void>;
 
+    /**
+     * Match file paths in deletes, renames and filters ignoring case (via `filePathKey`)
+     */
+    setCaseInsensitivePaths(enabled: boolean): void;
+
+    /**
+     * Store and search per-paragraph sub-vectors (max-sim) where the backend supports it
+     */
+    setMultiVector(enabled: boolean): void;
+
     /**
      * Insert or update with multiple named vectors
      */
@@ -68,17 +179,84 @@ export interface VectorBackend {
     delete(id: string): Promise<void>;
 
     /**
-     * Delete all chunks for a file
+     * Delete all chunks for a file, or only those inside/outside one index run
+     */
+    deleteByFilePath(filePath: string, scope?: IndexRunScope): Promise<void>;
+
+    /**
+     * Get chunk count and latest index time for a file, null if not indexed
+     */
+    getPathInfo(filePath: string): Promise<PathInfo | null>;
+
+    /**
+     * Get all chunks of a file ordered by start line, without a query vector
+     */
+    getByFilePath(filePath: string): Promise<SearchResult[]>;
+
+    /**
+     * Move all chunks of a file to a new path without re-embedding, returns the number updated
+     */
+    renameFilePath(oldPath: string, newPath: string): Promise<number>;
+
+    /**
+     * Delete all points of a given type, returns the number of deleted points
      */
-    deleteByFilePath(filePath: string): Promise<void>;
+    deleteByType(type: PointType): Promise<number>;
+
+    /**
+     * Delete all points carrying a tag, returns the number of deleted points
+     */
+    deleteByTag(tag: string): Promise<number>;
+
+    /**
+     * Top `topK` values of a payload field by number of points, most frequent first (ties by value)
+     */
+    facetCounts(field: FacetField, topK: number): Promise<FacetCount[]>;
 
     /**
      * Get total count of vectors
      */
     count(): Promise<number>;
 
+    /**
+     * Check connectivity and report collection name and dimension
+     */
+    getHealth(): Promise<BackendHealth>;
+
+    /**
+     * Write every point as one JSON line, page by page; returns the number of points written
+     */
+    exportPoints(
+        write: (line: string) => void | Promise<void>,
+        options?: { includeVectors?: boolean }
+    ): Promise<number>;
+
+    /**
+     * Restore points from exportPoints lines (exported with vectors), keeping their ids
+     * With `recreate` the collection is cleared first; lines with a wrong dimension are skipped
+     */
+    importPoints(lines: string[], options?: { recreate?: boolean }): Promise<ImportReport>;
+
+    /**
+     * Replace the vectors of every chunk point page by page, keeping ids and payload
+     * Paragraph sub-vectors are replaced too, or dropped when embed returns none, so no
+     * vector from the old model is left; concept points are left alone; a point whose
+     * embed call throws is counted as failed
+     */
+    reembedPoints(
+        embed: (metadata: Record<string, any>) => Promise<Pick<MultiVectorItem, 'vectors' | 'subVectors'>>,
+        onProgress?: (progress: ReembedReport) => void
+    ): Promise<ReembedReport>;
+
+    /**
+     * Ask the backend to (re)build its search index after bulk writes
+     * Returns the collection status right after the request (e.g. "yellow" while optimizing), null if unknown
+     */
+    optimize(): Promise<string | null>;
+
     /**
      * Clear all data
+     * When a dimension is given the collection is recreated right away with that vector size
      */
-    clear(): Promise<void>;
+    clear(dimension?: number): Promise<void>;
 }
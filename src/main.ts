@@ -9,23 +9,31 @@ import {
 import { EmbeddingService } from "./services/embedding-service";
 import type { VectorBackend } from "./services/vector-backend";
 import { QdrantBackend } from "./services/qdrant-backend";
+import { createVectorBackend } from "./services/vector-backend-factory";
 import { Chunker } from "./services/chunker";
 import { MetadataExtractor } from "./services/metadata-extractor";
 import { VectorIndexManager } from "./services/vector-index-manager";
+import {
+	IndexJobQueue,
+	formatShutdownSummary,
+} from "./services/index-job-queue";
 import { VIEW_TYPE_INDEX_SEARCH, VIEW_TYPE_CONCEPT } from "./core/constants";
 import { ParagraphDetector } from "./services/paragraph-detector";
 import { FrontmatterService } from "./services/frontmatter-service";
 import { ConceptRegistry } from "./services/concept-registry";
 import { SearchService } from "./services/search-service";
+import type { CalibrationPair } from "./services/search-service";
 import type {
 	ExtractedConceptWithMatch,
 	ConfirmedConcept,
 } from "./core/types/concept";
-import type { BaseModelConfig } from "./core/types/setting";
+import type { BaseModelConfig, ScoreCalibration } from "./core/types/setting";
+import { DEFAULT_INDEXING_CONFIG } from "./core/types/setting";
 import { SettingsManager } from "./core/settings/settings-manager";
 import { getErrorMessage } from "@utils/error";
 import { ContentPreprocessor } from "./services/content-preprocessor";
 import { SemanticChunker } from "./services/semantic-chunker";
+import { runPreflight } from "./services/preflight";
 
 export default class MemoEchoPlugin extends Plugin {
 	private indexSearchView: IndexSearchView | null = null;
@@ -46,6 +54,7 @@ export default class MemoEchoPlugin extends Plugin {
 	contentPreprocessor!: ContentPreprocessor;
 	semanticChunker!: SemanticChunker;
 	indexManager!: VectorIndexManager;
+	indexJobs!: IndexJobQueue;
 
 	// v0.5.0 services
 	frontmatterService!: FrontmatterService;
@@ -87,24 +96,54 @@ export default class MemoEchoPlugin extends Plugin {
 		console.log("📝 Loaded settings:", this.settings);
 
 		// Initialize services with saved settings
-		this.embeddingService = new EmbeddingService(
-			this.convertToEmbeddingConfig(this.settings.embeddingConfig),
-		);
+		this.embeddingService = new EmbeddingService({
+			...this.convertToEmbeddingConfig(this.settings.embeddingConfig),
+			ollamaKeepAlive: this.settings.embeddingKeepAlive,
+			ollamaHeaders: this.settings.embeddingHeaders,
+			maxInputChars: this.settings.embeddingMaxInputChars,
+		});
 		console.log(
 			`🤖 Embedding service initialized: ${this.settings.embeddingConfig.provider}`,
 		);
 
-		// VectorBackend - using Qdrant by default (v0.5.0)
-		this.vectorBackend = new QdrantBackend(
-			this.settings.qdrantCollection,
-			this.settings.qdrantUrl,
-			this.embeddingService, // Pass embeddingService for dimension detection
-		);
+		// VectorBackend - Qdrant by default (v0.5.0), or in memory
+		const backend = createVectorBackend({
+			kind: this.settings.vectorBackend ?? "qdrant",
+			collectionName: this.settings.qdrantCollection,
+			qdrantUrl: this.settings.qdrantUrl,
+			embeddingService: this.embeddingService, // Pass embeddingService for dimension detection
+			tuning: this.settings.qdrantTuning,
+			connection: {
+				timeoutMs: this.settings.qdrantTimeoutMs,
+				retries: this.settings.qdrantConnectRetries,
+			},
+		});
+		this.vectorBackend = backend;
 		console.log(
-			`🗄️ Vector backend initialized: Qdrant @ ${this.settings.qdrantUrl}`,
+			backend instanceof QdrantBackend
+				? `🗄️ Vector backend initialized: Qdrant @ ${this.settings.qdrantUrl}`
+				: "🗄️ Vector backend initialized: in memory (not persisted)",
+		);
+		this.vectorBackend.setCaseInsensitivePaths(
+			this.settings.indexing.caseInsensitivePaths,
+		);
+		this.vectorBackend.setMultiVector(
+			this.settings.indexing.multiVector ?? false,
 		);
+		// Qdrant/Ollama may start after Obsidian; check in the background without blocking load
+		this.runStartupPreflight();
 
-		this.chunker = new Chunker(500);
+		this.chunker = new Chunker(
+			this.settings.indexing.maxChunkSize ??
+				DEFAULT_INDEXING_CONFIG.maxChunkSize,
+			{
+				includeHeadingInContent:
+					this.settings.indexing.includeHeadingInContent ?? true,
+				splitOnHr: this.settings.indexing.splitOnHr ?? false,
+				emptySections: this.settings.indexing.emptySections ?? "keep",
+				oversizedPolicy: this.settings.indexing.oversizedPolicy ?? "split",
+			},
+		);
 		console.log("✂️ Chunker initialized");
 
 		// v0.2.0: Initialize metadata extractor
@@ -124,6 +163,7 @@ export default class MemoEchoPlugin extends Plugin {
 		this.searchService = new SearchService(
 			this.embeddingService,
 			this.vectorBackend,
+			this.settings.search,
 		);
 		console.log("🔍 Search service initialized (v0.7.0)");
 
@@ -136,7 +176,9 @@ export default class MemoEchoPlugin extends Plugin {
 			this.contentPreprocessor,
 			this.semanticChunker,
 			50 * 1024 * 1024, // 50MB cache
+			this.settings.indexing,
 		);
+		this.indexJobs = new IndexJobQueue(this.indexManager);
 
 		// v0.5.0: Initialize frontmatter service
 		this.frontmatterService = new FrontmatterService(
@@ -147,7 +189,7 @@ export default class MemoEchoPlugin extends Plugin {
 
 		// Initialize concept registry
 		this.conceptRegistry = new ConceptRegistry(
-			this.vectorBackend as QdrantBackend,
+			backend,
 			this.embeddingService,
 			{
 				similarityThreshold: 0.85,
@@ -168,6 +210,19 @@ export default class MemoEchoPlugin extends Plugin {
 						this.convertToEmbeddingConfig(config),
 					);
 				},
+				embeddingRequest: (config) => {
+					this.embeddingService.updateConfig({
+						...(config.embeddingKeepAlive !== undefined && {
+							ollamaKeepAlive: config.embeddingKeepAlive,
+						}),
+						...(config.embeddingHeaders !== undefined && {
+							ollamaHeaders: config.embeddingHeaders,
+						}),
+						...(config.embeddingMaxInputChars !== undefined && {
+							maxInputChars: config.embeddingMaxInputChars,
+						}),
+					});
+				},
 				// Adapter: convert BaseModelConfig (baseUrl, model, apiKey) to MetadataExtractorConfig
 				llm: (config) => {
 					const llmConfig: Partial<
@@ -206,6 +261,32 @@ export default class MemoEchoPlugin extends Plugin {
 						...config,
 					};
 				},
+				qdrantTuning: (config) => {
+					if (this.vectorBackend instanceof QdrantBackend) {
+						this.vectorBackend.setCollectionTuning(config);
+					}
+				},
+				indexing: (config) => {
+					this.indexManager.updateConfig(config);
+					this.chunker.updateOptions({
+						maxChunkSize: config.maxChunkSize,
+						includeHeadingInContent: config.includeHeadingInContent,
+						splitOnHr: config.splitOnHr,
+						emptySections: config.emptySections,
+						oversizedPolicy: config.oversizedPolicy,
+					});
+					if (config.caseInsensitivePaths !== undefined) {
+						this.vectorBackend.setCaseInsensitivePaths(
+							config.caseInsensitivePaths,
+						);
+					}
+					if (config.multiVector !== undefined) {
+						this.vectorBackend.setMultiVector(config.multiVector);
+					}
+				},
+				search: (config) => {
+					this.searchService.updateConfig(config);
+				},
 			},
 		);
 		console.log("⚙️ Settings manager initialized");
@@ -261,6 +342,64 @@ export default class MemoEchoPlugin extends Plugin {
 			},
 		});
 
+		// Fire-and-forget vault sync; progress is polled with the status command
+		this.addCommand({
+			id: "index-vault-in-background",
+			name: "后台同步 Vault",
+			callback: () => {
+				const paths = this.app.vault
+					.getFiles()
+					.filter((file) => this.indexManager.isIndexable(file.path))
+					.map((file) => file.path);
+				this.indexJobs.enqueue(paths, (path) =>
+					this.app.vault.adapter.read(path),
+				);
+				new Notice(`🔄 已加入后台同步: ${paths.length} 个文件`);
+			},
+		});
+
+		this.addCommand({
+			id: "show-index-job-status",
+			name: "查看后台同步进度",
+			callback: () => {
+				const job = this.indexJobs.listJobs().pop();
+				if (!job) {
+					new Notice("暂无后台同步任务");
+					return;
+				}
+				const states = {
+					queued: "排队中",
+					running: "进行中",
+					completed: "已完成",
+					failed: "失败",
+					cancelled: "已取消",
+				};
+				new Notice(
+					`后台同步${states[job.state]}: ${job.processed}/${job.total} 个文件，失败 ${job.errors.length}`,
+				);
+			},
+		});
+
+		// Keep indexed paths in sync when notes are renamed or moved
+		this.registerEvent(
+			this.app.vault.on("rename", (file, oldPath) => {
+				if (
+					!(file instanceof TFile) ||
+					!this.indexManager.isIndexable(file.path)
+				) {
+					return;
+				}
+				this.indexManager
+					.renameFile(oldPath, file.path)
+					.catch((error) =>
+						console.warn(
+							`[MemoEcho] Failed to rename indexed path ${oldPath}:`,
+							error,
+						),
+					);
+			}),
+		);
+
 		// v0.2.0: Setup paragraph detector
 		this.setupParagraphDetector();
 
@@ -272,7 +411,7 @@ export default class MemoEchoPlugin extends Plugin {
 		this.addSettingTab(new MemoEchoSettingTab(this.app, this));
 	}
 
-	async onunload() {
+	onunload() {
 		console.log("Unloading Memo Echo Plugin");
 
 		// Cleanup
@@ -289,6 +428,21 @@ export default class MemoEchoPlugin extends Plugin {
 
 		this.app.workspace.detachLeavesOfType(VIEW_TYPE_INDEX_SEARCH);
 		this.app.workspace.detachLeavesOfType(VIEW_TYPE_CONCEPT);
+
+		// Obsidian doesn't await onunload: drain background jobs after the synchronous teardown
+		if (this.indexJobs) {
+			this.indexJobs
+				.shutdown()
+				.then((summary) =>
+					console.log(
+						"[MemoEcho] Index jobs at shutdown:",
+						formatShutdownSummary(summary),
+					),
+				)
+				.catch((error) =>
+					console.error("[MemoEcho] Index job shutdown failed:", error),
+				);
+		}
 	}
 
 	async activateUnifiedSearchView() {
@@ -910,4 +1064,43 @@ export default class MemoEchoPlugin extends Plugin {
 		await this.saveData(this.settings);
 		console.log("💾 Settings saved:", this.settings);
 	}
+
+	/**
+	 * Fit the search score calibration from queries with a known relevant note
+	 * and save it with the search settings, so `calibrated` searches survive a restart
+	 */
+	async calibrateSearchScores(
+		pairs: CalibrationPair[],
+	): Promise<ScoreCalibration> {
+		const calibration = await this.searchService.calibrate(pairs);
+		const result = await this.settingsManager.updateSearch({ calibration });
+		if (!result.success) {
+			throw new Error(result.errors?.[0]?.message);
+		}
+		return calibration;
+	}
+
+	/**
+	 * Check the embedding provider and Qdrant once on startup
+	 * On failure the plugin stays loaded (degraded) and tells the user what to fix
+	 */
+	private async runStartupPreflight(): Promise<void> {
+		const result = await runPreflight({
+			checkEmbedding: () => this.embeddingService.checkHealth(),
+			checkVectorStore: () => this.vectorBackend.initialize(),
+		});
+
+		if (result.ready) {
+			console.log("✅ Preflight passed: embedding service and Qdrant are ready");
+			return;
+		}
+
+		for (const problem of result.problems) {
+			console.warn("⚠️ Preflight:", problem);
+		}
+		new Notice(
+			`⚠️ Memo Echo 依赖未就绪，索引和搜索暂不可用:\n${result.problems.join("\n")}`,
+			10000,
+		);
+	}
 }
@@ -0,0 +1,322 @@
+/**
+ * Chunker Unit Tests
+ */
+
+import { describe, it, expect } from 'vitest';
+import { Chunker, analyzeChunks, createChunkStrategy, getCalloutLabels } from '../chunker';
+
+describe('Chunker', () => {
+    describe('Newline Normalization', () => {
+        it('should produce the same chunks for mixed line endings', () => {
+            const chunker = new Chunker(500);
+            const unix = '# Title\nIntro line\n## Section\nBody text\n';
+            const mixed = '# Title\r\nIntro line\r## Section\r\nBody text\n';
+
+            const expected = chunker.chunk(unix);
+            const chunks = chunker.chunk(mixed);
+
+            expect(chunks).toEqual(expected);
+            expect(chunks).toHaveLength(2);
+            expect(chunks[0].header_path).toBe('# Title');
+            expect(chunks[1].header_path).toBe('# Title > ## Section');
+            expect(chunks[1].start_line).toBe(3);
+            expect(chunks[1].content).not.toContain('\r');
+        });
+    });
+
+    describe('Header Path Level Skips', () => {
+        it('should attach a skipped-down heading to the nearest shallower one', () => {
+            const chunks = new Chunker(500).chunk('# A\ntext\n### C\nmore\n');
+
+            expect(chunks[1].headers).toEqual([
+                { level: 1, text: 'A' },
+                { level: 3, text: 'C' },
+            ]);
+            expect(chunks[1].header_path).toBe('# A > ### C');
+        });
+
+        it('should reset the path when skipping back up', () => {
+            const chunks = new Chunker(500).chunk('### C\ntext\n# A\nmore\n## B\nend\n');
+
+            expect(chunks.map(c => c.header_path)).toEqual([
+                '### C',
+                '# A',
+                '# A > ## B',
+            ]);
+        });
+    });
+
+    describe('Heading Inclusion', () => {
+        it('should drop the heading line from content when disabled', () => {
+            const content = '# Title\nIntro line\n## Section\nBody text\n';
+            const chunks = new Chunker(500, { includeHeadingInContent: false }).chunk(content);
+
+            expect(chunks).toHaveLength(2);
+            expect(chunks[1].content).toBe('Body text\n');
+            expect(chunks[1].content).not.toContain('## Section');
+            expect(chunks[1].header_path).toBe('# Title > ## Section');
+            expect(chunks[1].start_line).toBe(4);
+            for (const chunk of chunks) {
+                expect(content.slice(chunk.startPos, chunk.endPos)).toBe(chunk.content);
+            }
+        });
+
+        it('should keep the heading line by default', () => {
+            const chunks = new Chunker(500).chunk('# Title\nIntro line\n');
+
+            expect(chunks[0].content).toBe('# Title\nIntro line\n');
+            expect(chunks[0].startPos).toBe(0);
+        });
+    });
+
+    describe('Empty Sections', () => {
+        const content = '# A\n## B\ntext\n';
+
+        it('should keep a heading-only section as its own chunk by default', () => {
+            const chunks = new Chunker(500).chunk(content);
+
+            expect(chunks.map(c => c.content)).toEqual(['# A\n', '## B\ntext\n']);
+        });
+
+        it('should merge an empty section into the next one', () => {
+            const chunks = new Chunker(500, { emptySections: 'merge' }).chunk(content);
+
+            expect(chunks).toHaveLength(1);
+            expect(chunks[0].content).toBe(content);
+            expect(chunks[0].header_path).toBe('# A > ## B');
+            expect(chunks[0].start_line).toBe(1);
+        });
+
+        it('should skip an empty section without losing it from the path', () => {
+            const chunks = new Chunker(500, { emptySections: 'skip' }).chunk(content);
+
+            expect(chunks).toHaveLength(1);
+            expect(chunks[0].content).toBe('## B\ntext\n');
+            expect(chunks[0].header_path).toBe('# A > ## B');
+        });
+    });
+
+    describe('Horizontal Rule Splitting', () => {
+        it('should split a section at thematic breaks keeping the header path', () => {
+            const content = '## Ideas\nfirst idea\n\n---\n\nsecond idea\n';
+            const chunks = new Chunker(500, { splitOnHr: true }).chunk(content);
+
+            expect(chunks).toHaveLength(2);
+            expect(chunks[0].content).toContain('first idea');
+            expect(chunks[1].content).toContain('second idea');
+            expect(chunks[0].header_path).toBe('## Ideas');
+            expect(chunks[1].header_path).toBe(chunks[0].header_path);
+            for (const chunk of chunks) {
+                expect(content.slice(chunk.startPos, chunk.endPos)).toBe(chunk.content);
+            }
+        });
+
+        it('should not split on setext underlines or fenced rules', () => {
+            const content = '## Ideas\nSubtitle\n---\n```\n***\n```\n';
+
+            expect(new Chunker(500, { splitOnHr: true }).chunk(content)).toHaveLength(1);
+        });
+    });
+
+    describe('Runtime Options', () => {
+        it('should report updated options and use them for the next chunk call', () => {
+            const chunker = new Chunker(500);
+            const content = Array.from({ length: 20 }, (_, i) => `line ${i} with some text`).join('\n');
+            expect(chunker.chunk(content)).toHaveLength(1);
+
+            chunker.updateOptions({ maxChunkSize: 100, splitOnHr: true });
+
+            expect(chunker.getOptions()).toEqual({ maxChunkSize: 100, includeHeadingInContent: true, splitOnHr: true });
+            const chunks = chunker.chunk(content);
+            expect(chunks.length).toBeGreaterThan(1);
+            expect(chunks.every(c => c.content.length <= 100)).toBe(true);
+        });
+
+        it('should reject an invalid chunk size and keep the previous one', () => {
+            const chunker = new Chunker(500);
+
+            expect(() => chunker.updateOptions({ maxChunkSize: 0 })).toThrow('maxChunkSize');
+            expect(chunker.getOptions().maxChunkSize).toBe(500);
+        });
+    });
+
+    describe('Heading Text', () => {
+        it('should strip closing hashes and block ids from heading text', () => {
+            const chunks = new Chunker(500).chunk('# Guide\nintro\n## Title ##\nbody\n### Sub ^abc-123\nmore\n## C# tips\nend\n');
+
+            expect(chunks.map(c => c.headers[c.headers.length - 1])).toEqual([
+                { level: 1, text: 'Guide' },
+                { level: 2, text: 'Title' },
+                { level: 3, text: 'Sub' },
+                { level: 2, text: 'C# tips' },
+            ]);
+            expect(chunks[2].header_path).toBe('# Guide > ## Title > ### Sub');
+        });
+    });
+
+    describe('Plain Text', () => {
+        it('should produce content-only chunks without header paths', () => {
+            const chunks = new Chunker(500).chunkPlainText('# not a heading\nplain text line\n');
+
+            expect(chunks).toHaveLength(1);
+            expect(chunks[0].header_path).toBe('');
+            expect(chunks[0].headers).toEqual([]);
+            expect(chunks[0].content).toBe('# not a heading\nplain text line\n');
+        });
+    });
+
+    describe('analyzeChunks', () => {
+        it('should summarize chunk counts and lengths', () => {
+            const report = analyzeChunks('# A\none\n# B\ntwo two\n', { maxChunkSize: 100 });
+
+            expect(report.count).toBe(2);
+            expect(report.minLen).toBe('# A\none\n'.length);
+            expect(report.maxLen).toBe('# B\ntwo two\n'.length);
+            expect(report.avgLen).toBe((8 + 12) / 2);
+            expect(report.histogram.reduce((sum, b) => sum + b.count, 0)).toBe(2);
+            expect(report.histogram[0]).toEqual({ upTo: 20, count: 2 });
+        });
+
+        it('should never report chunks longer than the configured maximum', () => {
+            const longLine = 'x'.repeat(250);
+            const content = `# Long\n${longLine}\n${'short line\n'.repeat(30)}`;
+
+            const report = analyzeChunks(content, { maxChunkSize: 100 });
+
+            expect(report.count).toBeGreaterThan(3);
+            expect(report.maxLen).toBeLessThanOrEqual(100);
+        });
+
+        it('should chunk with the given options and strategy like indexing does', () => {
+            const content = `# Blob\n${'A'.repeat(300)}\n\nfirst paragraph\n\nsecond paragraph\n`;
+            const chunker = new Chunker(100, { oversizedPolicy: 'skip', includeHeadingInContent: false });
+
+            const report = analyzeChunks(content, chunker.getOptions());
+            const paragraphs = analyzeChunks(content, { ...chunker.getOptions(), strategy: 'paragraph' });
+
+            expect(report.count).toBe(chunker.chunk(content).length);
+            expect(report.maxLen).toBe(301);
+            expect(paragraphs.count).toBe(createChunkStrategy('paragraph', chunker).chunk(content).length);
+            expect(analyzeChunks(content, { maxChunkSize: 100 }).maxLen).toBeLessThanOrEqual(100);
+        });
+    });
+
+    describe('Long Line Splitting', () => {
+        it('should keep chunk sizes uniform and reconstruct the content exactly', () => {
+            const content = 'x'.repeat(5000) + '\n' + 'short line\n'.repeat(10);
+
+            const chunks = new Chunker(500).chunkPlainText(content);
+
+            expect(chunks.map(c => c.content).join('')).toBe(content);
+            expect(chunks.slice(0, -1).every(c => c.content.length === 500)).toBe(true);
+            expect(chunks[chunks.length - 1].content).toBe('\n' + 'short line\n'.repeat(10));
+            expect(chunks.every(c => content.slice(c.startPos, c.endPos) === c.content)).toBe(true);
+        });
+    });
+
+    describe('Oversized Lines', () => {
+        const content = 'intro text\n' + 'A'.repeat(1200) + '\noutro text\n';
+
+        it('should hard split an unbreakable line by default', () => {
+            const chunks = new Chunker(100).chunkPlainText(content);
+
+            expect(chunks.every(c => c.content.length <= 100)).toBe(true);
+            expect(chunks.some(c => c.oversized)).toBe(false);
+        });
+
+        it('should keep an unbreakable line whole and flag it for other policies', () => {
+            const chunks = new Chunker(100, { oversizedPolicy: 'skip' }).chunkPlainText(content);
+
+            expect(chunks.map(c => c.content.length)).toEqual([11, 1201, 11]);
+            expect(chunks.map(c => Boolean(c.oversized))).toEqual([false, true, false]);
+            expect(chunks.map(c => c.content).join('')).toBe(content);
+        });
+
+        it('should not flag a line of exactly the maximum length', () => {
+            const content = 'intro text\n' + 'A'.repeat(100) + '\noutro text\n';
+
+            const chunks = new Chunker(100, { oversizedPolicy: 'skip' }).chunkPlainText(content);
+
+            expect(chunks.some(c => c.oversized)).toBe(false);
+            expect(chunks.some(c => c.content.includes('A'.repeat(100)))).toBe(true);
+            expect(chunks.map(c => c.content).join('')).toBe(content);
+        });
+    });
+
+    describe('Callouts', () => {
+        const callout = [
+            '> [!warning] Cache invalidation',
+            '> Keys must include the schema version,',
+            '> otherwise stale entries survive a migration.',
+            '> Clear the cache after every deploy.',
+            '',
+        ].join('\n');
+
+        it('should keep a multi-line callout whole inside a long section', () => {
+            const content = `# Notes\n${'filler line here\n'.repeat(6)}${callout}${'more text\n'.repeat(4)}`;
+
+            const chunks = new Chunker(200).chunk(content);
+
+            expect(chunks.length).toBeGreaterThan(1);
+            expect(chunks.filter(c => c.content.includes('> [!warning]'))).toHaveLength(1);
+            expect(chunks.some(c => c.content.includes(callout))).toBe(true);
+            expect(chunks.map(c => c.content).join('')).toBe(content);
+        });
+
+        it('should label callouts by type and title', () => {
+            expect(getCalloutLabels(`${callout}> [!note]-\n> folded`)).toEqual([
+                'warning: Cache invalidation',
+                'note',
+            ]);
+        });
+    });
+
+    describe('Chunk Strategies', () => {
+        const content = [
+            '# Intro',
+            'First paragraph line one.',
+            'First paragraph line two.',
+            '',
+            'Second paragraph is here.',
+            '',
+            '## Details',
+            'Third paragraph under details.',
+            '',
+        ].join('\n');
+        const chunkWith = (name: 'header' | 'paragraph' | 'fixed') =>
+            createChunkStrategy(name, new Chunker(100)).chunk(content);
+
+        it('should reproduce the content exactly with every strategy', () => {
+            for (const name of ['header', 'paragraph', 'fixed'] as const) {
+                const chunks = chunkWith(name);
+                expect(chunks.map(c => c.content).join('')).toBe(content);
+                expect(chunks.every(c => content.slice(c.startPos, c.endPos) === c.content)).toBe(true);
+            }
+        });
+
+        it('should split by heading with header paths', () => {
+            const chunks = chunkWith('header');
+
+            expect(chunks.map(c => c.header_path)).toEqual(['# Intro', '# Intro > ## Details']);
+        });
+
+        it('should pack whole paragraphs without header paths', () => {
+            const chunks = chunkWith('paragraph');
+
+            expect(chunks.map(c => c.content)).toEqual([
+                '# Intro\nFirst paragraph line one.\nFirst paragraph line two.\n\nSecond paragraph is here.\n\n',
+                '## Details\nThird paragraph under details.\n',
+            ]);
+            expect(chunks.every(c => c.header_path === '')).toBe(true);
+            expect(chunks.map(c => [c.start_line, c.end_line])).toEqual([[1, 7], [7, 9]]);
+        });
+
+        it('should fill fixed-size chunks line by line, ignoring headings', () => {
+            const chunks = chunkWith('fixed');
+
+            expect(chunks).toHaveLength(2);
+            expect(chunks[0].content.endsWith('## Details\n')).toBe(true);
+            expect(chunks[1].content).toBe('Third paragraph under details.\n');
+        });
+    });
+});
@@ -0,0 +1,29 @@
+/**
+ * ContentPreprocessor Unit Tests
+ */
+
+import { describe, it, expect } from 'vitest';
+import { ContentPreprocessor } from '../content-preprocessor';
+
+describe('ContentPreprocessor', () => {
+    const preprocess = (content: string) => new ContentPreprocessor().preprocess(content).cleaned;
+
+    describe('HTML Images', () => {
+        it('should replace an <img> with src before other attributes', () => {
+            expect(preprocess('text\n<img src="pics/cat.png" alt="cat" width="200">\nmore'))
+                .toBe('text\n[image]\nmore');
+        });
+
+        it('should replace an <img> with src after other attributes', () => {
+            expect(preprocess('<img alt="cat" width="200" src="pics/cat.png" />')).toBe('[image]');
+        });
+
+        it('should accept single-quoted src', () => {
+            expect(preprocess("<IMG alt='cat' src='pics/cat.png'>")).toBe('[image]');
+        });
+
+        it('should keep lines that only mention img without a src', () => {
+            expect(preprocess('Use the <img> tag for pictures')).toBe('Use the <img> tag for pictures');
+        });
+    });
+});
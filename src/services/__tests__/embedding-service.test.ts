@@ -0,0 +1,261 @@
+/**
+ * EmbeddingService Unit Tests
+ */
+
+import { describe, it, expect, afterEach, vi } from 'vitest';
+import { EmbeddingService, coerceEmbedding, parseHeaderLines } from '../embedding-service';
+
+describe('EmbeddingService', () => {
+    afterEach(() => {
+        vi.unstubAllGlobals();
+    });
+
+    describe('Concurrency Limit', () => {
+        it('should never exceed the configured number of in-flight requests', async () => {
+            let inFlight = 0;
+            let maxObserved = 0;
+
+            vi.stubGlobal('fetch', vi.fn(async () => {
+                inFlight++;
+                maxObserved = Math.max(maxObserved, inFlight);
+                await new Promise((resolve) => setTimeout(resolve, 5));
+                inFlight--;
+                return {
+                    ok: true,
+                    json: async () => ({ embeddings: [[0.1, 0.2]] }),
+                };
+            }));
+
+            const service = new EmbeddingService({
+                provider: 'ollama',
+                ollamaUrl: 'http://localhost:11434',
+                ollamaModel: 'bge-m3:latest',
+                maxConcurrency: 2,
+            });
+
+            const texts = Array.from({ length: 8 }, (_, i) => `text ${i}`);
+            const vectors = await Promise.all(texts.map((text) => service.embed(text)));
+
+            expect(vectors).toHaveLength(8);
+            expect(maxObserved).toBeLessThanOrEqual(2);
+            expect(maxObserved).toBeGreaterThan(0);
+        });
+    });
+
+    describe('Batch Ordering', () => {
+        // Later texts answer first, and each vector encodes its text's number
+        const stubOutOfOrderFetch = () => vi.stubGlobal('fetch', vi.fn(async (_url: string, init: { body: string }) => {
+            const input: string = JSON.parse(init.body).input;
+            const n = Number(input.split(' ')[1]);
+            await new Promise((resolve) => setTimeout(resolve, (10 - n) * 2));
+            if (input === 'text 3') {
+                return { ok: false, status: 500, statusText: 'boom', text: async () => 'boom' };
+            }
+            return { ok: true, status: 200, json: async () => ({ embeddings: [[n, n]] }) };
+        }));
+
+        const createService = () => new EmbeddingService({
+            provider: 'ollama',
+            ollamaUrl: 'http://localhost:11434',
+            ollamaModel: 'bge-m3:latest',
+            maxConcurrency: 4,
+        });
+
+        it('should pair each vector with its input even when requests complete out of order', async () => {
+            stubOutOfOrderFetch();
+            const texts = [0, 1, 2, 4, 5, 6].map((n) => `text ${n}`);
+
+            const vectors = await createService().embedBatch(texts) as number[][];
+
+            expect(vectors).toEqual([[0, 0], [1, 1], [2, 2], [4, 4], [5, 5], [6, 6]]);
+        });
+
+        it('should keep failed slots aligned with continueOnError', async () => {
+            stubOutOfOrderFetch();
+            const texts = Array.from({ length: 6 }, (_, n) => `text ${n}`);
+
+            const result = await createService().embedBatch(texts, { continueOnError: true });
+
+            expect('vectors' in result && result.vectors).toEqual([[0, 0], [1, 1], [2, 2], null, [4, 4], [5, 5]]);
+            expect('failed' in result && result.failed.map((f) => f.index)).toEqual([3]);
+        });
+    });
+
+    describe('Input Limit', () => {
+        const captureInput = async (text: string, maxInputChars?: number) => {
+            const fetchMock = vi.fn(async (_url: string, _init: { body: string }) => ({
+                ok: true,
+                status: 200,
+                json: async () => ({ embeddings: [[0.1, 0.2]] }),
+            }));
+            vi.stubGlobal('fetch', fetchMock);
+
+            const service = new EmbeddingService({
+                provider: 'ollama',
+                ollamaUrl: 'http://localhost:11434',
+                ollamaModel: 'bge-m3:latest',
+                maxInputChars,
+            });
+            await service.embed(text);
+            return JSON.parse(fetchMock.mock.calls[0][1].body).input as string;
+        };
+
+        it('should truncate over-limit input at a sentence boundary', async () => {
+            const text = 'First sentence here. Second sentence goes on and on past the limit';
+
+            const input = await captureInput(text, 30);
+
+            expect(input).toBe('First sentence here.');
+            expect(input.length).toBeLessThanOrEqual(30);
+        });
+
+        it('should never split a multi-byte character and leave short input alone', async () => {
+            const input = await captureInput('😀'.repeat(30), 25);
+
+            expect(Array.from(input)).toHaveLength(25);
+            expect(await captureInput('short text', 40)).toBe('short text');
+        });
+    });
+
+    describe('Ollama Endpoint Fallback', () => {
+        it('should fall back to /api/embeddings when /api/embed returns 404', async () => {
+            const fetchMock = vi.fn(async (url: string) => {
+                if (url.endsWith('/api/embed')) {
+                    return { ok: false, status: 404, statusText: 'Not Found', text: async () => '404 page not found' };
+                }
+                return {
+                    ok: true,
+                    status: 200,
+                    json: async () => ({ embedding: [0.3, 0.4] }),
+                };
+            });
+            vi.stubGlobal('fetch', fetchMock);
+
+            const service = new EmbeddingService({
+                provider: 'ollama',
+                ollamaUrl: 'http://localhost:11434',
+                ollamaModel: 'nomic-embed-text',
+            });
+
+            expect(await service.embed('first')).toEqual([0.3, 0.4]);
+            expect(await service.embed('second')).toEqual([0.3, 0.4]);
+
+            // Capability is cached: /api/embed is only probed once
+            const probed = fetchMock.mock.calls.filter(([url]) => url.endsWith('/api/embed'));
+            expect(probed).toHaveLength(1);
+            expect(fetchMock).toHaveBeenCalledTimes(3);
+        });
+
+        it('should surface a model-not-found 404 without switching endpoints', async () => {
+            const fetchMock = vi.fn(async (_url: string) => ({
+                ok: false,
+                status: 404,
+                statusText: 'Not Found',
+                text: async () => JSON.stringify({ error: 'model "nomic-embd-text" not found, try pulling it first' }),
+            }));
+            vi.stubGlobal('fetch', fetchMock);
+
+            const service = new EmbeddingService({
+                provider: 'ollama',
+                ollamaUrl: 'http://localhost:11434',
+                ollamaModel: 'nomic-embd-text',
+            });
+
+            await expect(service.embed('first')).rejects.toThrow('model "nomic-embd-text" not found');
+            await expect(service.embed('second')).rejects.toThrow('not found');
+
+            // Both calls went to /api/embed; the legacy endpoint was never tried
+            expect(fetchMock.mock.calls.map(([url]) => url)).toEqual([
+                'http://localhost:11434/api/embed',
+                'http://localhost:11434/api/embed',
+            ]);
+        });
+    });
+
+    describe('Numeric Coercion', () => {
+        it('should accept integer and stringified elements in an Ollama response', async () => {
+            vi.stubGlobal('fetch', vi.fn(async () => ({
+                ok: true,
+                status: 200,
+                json: async () => ({ embeddings: [[0, 0.25, '-1.5', 2, ' 3e-2 ']] }),
+            })));
+
+            const service = new EmbeddingService({
+                provider: 'ollama',
+                ollamaUrl: 'http://localhost:11434',
+                ollamaModel: 'bge-m3:latest',
+            });
+
+            expect(await service.embed('text')).toEqual([0, 0.25, -1.5, 2, 0.03]);
+        });
+
+        it('should reject non-numeric elements', () => {
+            expect(() => coerceEmbedding([0.1, 'abc'])).toThrow('index 1');
+            expect(() => coerceEmbedding([0.1, ''])).toThrow('index 1');
+            expect(() => coerceEmbedding([null])).toThrow('index 0');
+            expect(() => coerceEmbedding([0.1, 'NaN'])).toThrow('index 1');
+        });
+    });
+
+    describe('Custom Headers', () => {
+        it('should send the configured headers with each Ollama request', async () => {
+            const fetchMock = vi.fn(async (_url: string, _init: { headers: Record<string, string> }) => ({
+                ok: true,
+                status: 200,
+                json: async () => ({ embeddings: [[0.1, 0.2]] }),
+            }));
+            vi.stubGlobal('fetch', fetchMock);
+
+            const service = new EmbeddingService({
+                provider: 'ollama',
+                ollamaUrl: 'http://localhost:11434',
+                ollamaModel: 'bge-m3:latest',
+                ollamaHeaders: { 'Authorization': 'Bearer secret', 'X-Api-Key': 'abc' },
+            });
+            await service.embed('text');
+
+            expect(fetchMock.mock.calls[0][1].headers).toEqual({
+                'Authorization': 'Bearer secret',
+                'X-Api-Key': 'abc',
+                'Content-Type': 'application/json',
+            });
+        });
+
+        it('should parse one header per line', () => {
+            expect(parseHeaderLines('Authorization: Bearer a:b\n\n: nameless\nX-Api-Key:  abc ')).toEqual({
+                'Authorization': 'Bearer a:b',
+                'X-Api-Key': 'abc',
+            });
+        });
+    });
+
+    describe('Ollama Keep Alive', () => {
+        const captureBody = async (ollamaKeepAlive?: string) => {
+            const fetchMock = vi.fn(async (_url: string, _init: { body: string }) => ({
+                ok: true,
+                status: 200,
+                json: async () => ({ embeddings: [[0.1, 0.2]] }),
+            }));
+            vi.stubGlobal('fetch', fetchMock);
+
+            const service = new EmbeddingService({
+                provider: 'ollama',
+                ollamaUrl: 'http://localhost:11434',
+                ollamaModel: 'bge-m3:latest',
+                ollamaKeepAlive,
+            });
+            await service.embed('text');
+
+            return JSON.parse(fetchMock.mock.calls[0][1].body);
+        };
+
+        it('should send the configured keep_alive with each request', async () => {
+            expect((await captureBody('1h')).keep_alive).toBe('1h');
+        });
+
+        it('should default to 30m and send 0 as a number', async () => {
+            expect((await captureBody()).keep_alive).toBe('30m');
+            expect((await captureBody('0')).keep_alive).toBe(0);
+        });
+    });
+});
@@ -0,0 +1,120 @@
+/**
+ * IndexJobQueue Unit Tests
+ */
+
+import { describe, it, expect, vi, afterEach } from 'vitest';
+import { IndexJobQueue, formatShutdownSummary } from '../index-job-queue';
+import { VectorIndexManager } from '../vector-index-manager';
+import { MemoryBackend } from '../memory-backend';
+import { Chunker } from '../chunker';
+import { ContentPreprocessor } from '../content-preprocessor';
+import type { EmbeddingService } from '../embedding-service';
+import type { MetadataExtractor } from '../metadata-extractor';
+import type { SemanticChunker } from '../semantic-chunker';
+import { DEFAULT_INDEXING_CONFIG } from '@core/types/setting';
+import type { IndexingConfig } from '@core/types/setting';
+
+describe('IndexJobQueue', () => {
+    let manager: VectorIndexManager;
+
+    const createQueue = (config: Partial<IndexingConfig> = {}) => {
+        const embed = vi.fn().mockResolvedValue([0.1, 0.2, 0.3]);
+        manager = new VectorIndexManager(
+            new MemoryBackend(),
+            { embed } as unknown as EmbeddingService,
+            new Chunker(500),
+            {
+                extract: vi.fn().mockResolvedValue({ summary: '', tags: [], category: '', concepts: [] }),
+            } as unknown as MetadataExtractor,
+            new ContentPreprocessor(),
+            {} as unknown as SemanticChunker,
+            1024 * 1024,
+            { ...DEFAULT_INDEXING_CONFIG, ...config },
+        );
+        return new IndexJobQueue(manager);
+    };
+
+    afterEach(() => {
+        manager?.stop();
+    });
+
+    it('should return a job id at once and report completion with counts when polled', async () => {
+        const queue = createQueue();
+        const files: Record<string, string> = {
+            'a.md': '# A\none\n',
+            'b.md': '# B\ntwo\n',
+            'c.md': '# C\nthree\n',
+        };
+        const read = async (path: string) => {
+            if (!(path in files)) {
+                throw new Error(`missing ${path}`);
+            }
+            return files[path];
+        };
+
+        const id = queue.enqueue(['a.md', 'b.md', 'missing.md', 'c.md'], read);
+        expect(['queued', 'running']).toContain(queue.getJob(id)?.state);
+
+        let job = queue.getJob(id);
+        while (job && job.state !== 'completed' && job.state !== 'failed') {
+            await new Promise(resolve => setTimeout(resolve, 5));
+            job = queue.getJob(id);
+        }
+
+        expect(job).toEqual({
+            id,
+            state: 'completed',
+            processed: 4,
+            total: 4,
+            errors: [{ filePath: 'missing.md', error: 'missing missing.md' }],
+        });
+    });
+
+    it('should run queued jobs one after another', async () => {
+        const queue = createQueue();
+        const read = async () => '# Note\nbody\n';
+
+        const first = queue.enqueue(['a.md'], read);
+        const second = queue.enqueue(['b.md', 'c.md'], read);
+        await queue.whenIdle();
+
+        expect(queue.listJobs().map(job => [job.id, job.state, job.processed])).toEqual([
+            [first, 'completed', 1],
+            [second, 'completed', 2],
+        ]);
+        expect(queue.getJob('unknown')).toBeUndefined();
+    });
+
+    it('should finish started files on shutdown and mark the rest of the work cancelled', async () => {
+        const queue = createQueue({ indexConcurrency: 1 });
+        let release = () => {};
+        const blocked = new Promise<void>(resolve => {
+            release = resolve;
+        });
+        const started: string[] = [];
+        const read = async (path: string) => {
+            started.push(path);
+            if (path === 'a.md') {
+                await blocked;
+            }
+            return '# Note\nbody\n';
+        };
+
+        const running = queue.enqueue(['a.md', 'b.md', 'c.md'], read);
+        const queued = queue.enqueue(['d.md'], read);
+        while (started.length === 0) {
+            await new Promise(resolve => setTimeout(resolve, 1));
+        }
+
+        const shutdown = queue.shutdown();
+        release();
+        const summary = await shutdown;
+
+        expect(started).toEqual(['a.md']);
+        expect(queue.getJob(running)).toMatchObject({ state: 'cancelled', processed: 1, total: 3, errors: [] });
+        expect(queue.getJob(queued)).toMatchObject({ state: 'cancelled', processed: 0 });
+        expect(summary).toEqual({ completed: 0, failed: 0, cancelled: 2, abandonedFiles: 3 });
+        expect(formatShutdownSummary(summary)).toBe('0 completed, 0 failed, 2 cancelled (3 file(s) not indexed)');
+        expect(queue.getJob(queue.enqueue(['e.md'], read))?.state).toBe('cancelled');
+    });
+});
@@ -0,0 +1,276 @@
+/**
+ * MemoryBackend Unit Tests
+ */
+
+import { describe, it, expect, beforeEach } from 'vitest';
+import { MemoryBackend } from '../memory-backend';
+import { VECTOR_NAMES } from '../vector-backend';
+import type { MultiVectorItem } from '../vector-backend';
+import { getAncestorFolders, toPathKey } from '@utils/path-utils';
+import { createVectorBackend } from '../vector-backend-factory';
+import { DEFAULT_QDRANT_TUNING_CONFIG } from '@core/types/setting';
+
+const item = (id: string, vector: number[], metadata: Record<string, any> = {}): MultiVectorItem => ({
+    id,
+    vectors: {
+        [VECTOR_NAMES.CONTENT]: vector,
+        [VECTOR_NAMES.SUMMARY]: vector,
+        [VECTOR_NAMES.TITLE]: vector,
+    },
+    metadata: { filePath: `${id}.md`, type: 'chunk', ...metadata },
+});
+
+describe('MemoryBackend', () => {
+    let backend: MemoryBackend;
+
+    beforeEach(async () => {
+        backend = new MemoryBackend();
+        await backend.upsertMultiVector(item('x', [1, 0, 0], { tags: ['alpha'] }));
+        await backend.upsertMultiVector(item('xy', [0.7, 0.7, 0]));
+        await backend.upsertMultiVector(item('z', [0, 0, 1], { type: 'concept' }));
+    });
+
+    it('should rank results by cosine similarity', async () => {
+        const results = await backend.searchWithFusion([1, 0.1, 0], { limit: 3 });
+
+        expect(results.map(r => r.id)).toEqual(['x', 'xy', 'z']);
+    });
+
+    it('should apply tag filters', async () => {
+        const results = await backend.searchWithFusion([0, 0, 1], { filter: { tags: ['alpha'] } });
+
+        expect(results.map(r => r.id)).toEqual(['x']);
+    });
+
+    it('should upsert by id and report stats', async () => {
+        await backend.upsertMultiVector(item('x', [0, 1, 0]));
+
+        expect(await backend.count()).toBe(3);
+        expect((await backend.getHealth()).dimension).toBe(3);
+    });
+
+    it('should delete by type and clear everything', async () => {
+        expect(await backend.deleteByType('concept')).toBe(1);
+        expect(await backend.count()).toBe(2);
+
+        await backend.clear();
+
+        expect(await backend.count()).toBe(0);
+        expect(await backend.searchWithFusion([1, 0, 0])).toEqual([]);
+    });
+
+    it('should filter on any ancestor folder', async () => {
+        await backend.upsertMultiVector(item('c', [1, 0, 0], {
+            filePath: 'a/b/c.md',
+            folders: getAncestorFolders('a/b/c.md'),
+        }));
+
+        const inFolder = async (folder: string) =>
+            (await backend.searchWithFusion([1, 0, 0], { filter: { folder } })).map(r => r.id);
+
+        expect(await inFolder('a')).toEqual(['c']);
+        expect(await inFolder('a/b/')).toEqual(['c']);
+        expect(await inFolder('x')).toEqual([]);
+    });
+
+    it('should match a chunk on its best paragraph sub-vector', async () => {
+        await backend.upsertMultiVector({
+            ...item('long', [0.6, 0.6, 0.5]),
+            subVectors: [[0.9, 0.1, 0], [0, 0.2, 0.9], [0, 1, 0]],
+        });
+
+        const results = await backend.searchWithFusion([0, 1, 0], { limit: 2 });
+
+        expect(results[0].id).toBe('long');
+    });
+
+    it('should match paths ignoring case via filePathKey and keep the key on rename', async () => {
+        await backend.upsertMultiVector(item('Notes/A', [0, 1, 0], { filePath: 'Notes/A.md', filePathKey: toPathKey('Notes/A.md') }));
+        backend.setCaseInsensitivePaths(true);
+
+        expect(await backend.renameFilePath('notes/a.md', 'Archive/B.md')).toBe(1);
+        const [renamed] = await backend.getByFilePath('archive/b.md');
+        expect(renamed.metadata).toMatchObject({
+            filePath: 'Archive/B.md',
+            filePathKey: 'archive/b.md',
+            folders: ['Archive'],
+        });
+
+        await backend.deleteByFilePath('ARCHIVE/b.md');
+        expect(await backend.getPathInfo('Archive/B.md')).toBeNull();
+    });
+
+    it('should keep each index run of a chunk and delete by run', async () => {
+        await backend.upsertMultiVector(item('n', [0, 1, 0], { indexRunId: 'old' }));
+        await backend.upsertMultiVector(item('n', [0, 1, 0], { indexRunId: 'new' }));
+        expect((await backend.getPathInfo('n.md'))?.chunks).toBe(2);
+
+        await backend.deleteByFilePath('n.md', { indexRunId: 'new' });
+        expect((await backend.getByFilePath('n.md')).map(r => r.metadata.indexRunId)).toEqual(['old']);
+
+        await backend.upsertMultiVector(item('n', [0, 1, 0], { indexRunId: 'new' }));
+        await backend.deleteByFilePath('n.md', { exceptIndexRunId: 'new' });
+        expect((await backend.getByFilePath('n.md')).map(r => r.metadata.indexRunId)).toEqual(['new']);
+    });
+
+    it('should register, match and count concepts like the Qdrant backend', async () => {
+        await backend.clear();
+        await backend.upsertConcept('rust', 'systems language', '[[_me/rust]]', [1, 0, 0], [1, 0, 0]);
+        await backend.upsertConcept('go', 'simple language', '[[_me/go]]', [0, 1, 0], [0, 1, 0]);
+
+        const [match] = await backend.searchSimilarConceptsStrict([0.99, 0.05, 0], { limit: 1 });
+        expect(match.payload).toMatchObject({ type: 'concept', concept: 'rust', noteCount: 1 });
+        expect(await backend.searchSimilarConceptsStrict([0, 0, 1])).toEqual([]);
+
+        await backend.updateConceptUsageWithVectors('rust', [1, 0, 0], [1, 0, 0]);
+        expect((await backend.getConcept('rust'))?.payload.noteCount).toBe(2);
+        expect((await backend.getConcept('rust'))?.id).toBe(match.id);
+
+        const firstPage = await backend.scrollConcepts({ limit: 1 });
+        const secondPage = await backend.scrollConcepts({ limit: 1, offset: firstPage.nextPage ?? undefined });
+        expect([...firstPage.points, ...secondPage.points].map(p => p.payload.concept)).toEqual(['rust', 'go']);
+        expect(secondPage.nextPage).toBeNull();
+    });
+
+    it('should be built by the backend factory when selected', () => {
+        const options = {
+            collectionName: 'notes',
+            qdrantUrl: 'http://localhost:6333',
+            tuning: DEFAULT_QDRANT_TUNING_CONFIG,
+            connection: {},
+            embeddingService: { getDimension: () => 3, embed: async () => [0, 0, 0] },
+        };
+
+        expect(createVectorBackend({ ...options, kind: 'memory' })).toBeInstanceOf(MemoryBackend);
+        expect(createVectorBackend({ ...options, kind: 'qdrant' })).not.toBeInstanceOf(MemoryBackend);
+    });
+
+    it('should count facet values, most frequent first', async () => {
+        await backend.upsertMultiVector(item('a', [1, 0, 0], { tags: ['rust', 'db'], folders: ['work'] }));
+        await backend.upsertMultiVector(item('b', [1, 0, 0], { tags: ['rust'], folders: ['work'] }));
+        await backend.upsertMultiVector(item('c', [1, 0, 0], { tags: ['db', 'rust'], folders: ['home'] }));
+
+        expect(await backend.facetCounts('tags', 3)).toEqual([
+            { value: 'rust', count: 3 },
+            { value: 'db', count: 2 },
+            { value: 'alpha', count: 1 },
+        ]);
+        expect(await backend.facetCounts('folders', 1)).toEqual([{ value: 'work', count: 2 }]);
+    });
+
+    it('should export one JSON line per point', async () => {
+        const lines: string[] = [];
+
+        const count = await backend.exportPoints((line) => {
+            lines.push(line);
+        });
+
+        expect(count).toBe(3);
+        expect(lines).toHaveLength(3);
+        const first = JSON.parse(lines[0]);
+        expect(first).toMatchObject({ id: 'x', filePath: 'x.md', type: 'chunk' });
+        expect(first.metadata.tags).toEqual(['alpha']);
+        expect(first.vectors).toBeUndefined();
+
+        await backend.exportPoints((line) => {
+            lines.push(line);
+        }, { includeVectors: true });
+        expect(JSON.parse(lines[3]).vectors[VECTOR_NAMES.CONTENT]).toEqual([1, 0, 0]);
+    });
+
+    it('should restore an export exactly', async () => {
+        const lines: string[] = [];
+        await backend.exportPoints((line) => {
+            lines.push(line);
+        }, { includeVectors: true });
+        const before = await backend.searchWithFusion([1, 0.1, 0], { limit: 3 });
+
+        await backend.clear();
+        const report = await backend.importPoints([...lines, 'not json', ''], { recreate: true });
+
+        expect(report).toEqual({ imported: 3, skipped: 1 });
+        expect(await backend.count()).toBe(3);
+        expect(await backend.searchWithFusion([1, 0.1, 0], { limit: 3 })).toEqual(before);
+    });
+
+    it('should skip points whose dimension differs from the collection', async () => {
+        const line = JSON.stringify({
+            id: 'w',
+            metadata: { filePath: 'w.md' },
+            vectors: {
+                [VECTOR_NAMES.CONTENT]: [1, 0],
+                [VECTOR_NAMES.SUMMARY]: [1, 0],
+                [VECTOR_NAMES.TITLE]: [1, 0],
+            },
+        });
+
+        expect(await backend.importPoints([line])).toEqual({ imported: 0, skipped: 1 });
+    });
+
+    it('should restore concept vectors and paragraph sub-vectors', async () => {
+        await backend.upsertMultiVector({ ...item('p', [0, 1, 0]), subVectors: [[0, 1, 0], [0, 0, 1]] });
+        const concept = JSON.stringify({
+            id: 'c',
+            metadata: { type: 'concept', concept: 'rust' },
+            vectors: { concept_vec: [0, 0, 1], concept_summary_vec: [0, 0, 1] },
+        });
+        const lines: string[] = [];
+        await backend.exportPoints((line) => {
+            lines.push(line);
+        }, { includeVectors: true });
+
+        const report = await backend.importPoints([...lines, concept], { recreate: true });
+
+        expect(report).toEqual({ imported: 5, skipped: 0 });
+        const exported: string[] = [];
+        await backend.exportPoints((line) => {
+            exported.push(line);
+        }, { includeVectors: true });
+        expect(exported.slice(0, 4)).toEqual(lines);
+        expect(JSON.parse(exported[4]).vectors).toEqual({ concept_vec: [0, 0, 1], concept_summary_vec: [0, 0, 1] });
+        expect((await backend.searchWithFusion([0, 0, 1], { limit: 10 })).map(r => r.id)).not.toContain('c');
+    });
+
+    it('should reject lines whose vectors mix dimensions', async () => {
+        const line = JSON.stringify({
+            id: 'm',
+            metadata: { filePath: 'm.md' },
+            vectors: { [VECTOR_NAMES.CONTENT]: [1, 0, 0], [VECTOR_NAMES.SUMMARY]: [1, 0], [VECTOR_NAMES.TITLE]: [1, 0, 0] },
+        });
+
+        expect(await backend.importPoints([line])).toEqual({ imported: 0, skipped: 1 });
+    });
+
+    it('should re-embed chunk points in place, keeping payloads and skipping concepts', async () => {
+        const exportAll = async () => {
+            const lines: string[] = [];
+            await backend.exportPoints((line) => {
+                lines.push(line);
+            }, { includeVectors: true });
+            return lines.map(line => JSON.parse(line));
+        };
+        const before = await exportAll();
+        const embed = async (metadata: Record<string, any>) => {
+            if (metadata.filePath === 'xy.md') {
+                throw new Error('embedding failed');
+            }
+            return {
+                vectors: {
+                    [VECTOR_NAMES.CONTENT]: [0, 1, 0],
+                    [VECTOR_NAMES.SUMMARY]: [0, 1, 0],
+                    [VECTOR_NAMES.TITLE]: [0, 1, 0],
+                },
+            };
+        };
+
+        const report = await backend.reembedPoints(embed);
+        const after = await exportAll();
+
+        expect(report).toEqual({ updated: 1, failed: 1 });
+        expect(after.map(p => p.metadata)).toEqual(before.map(p => p.metadata));
+        const vectorsOf = (points: any[], id: string) => points.find(p => p.id === id).vectors[VECTOR_NAMES.CONTENT];
+        expect(vectorsOf(after, 'x')).toEqual([0, 1, 0]);
+        expect(vectorsOf(after, 'xy')).toEqual(vectorsOf(before, 'xy'));
+        expect(vectorsOf(after, 'z')).toEqual([0, 0, 1]);
+    });
+});
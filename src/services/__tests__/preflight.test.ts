@@ -0,0 +1,53 @@
+/**
+ * Preflight Unit Tests
+ */
+
+import { describe, it, expect, vi } from 'vitest';
+import { runPreflight } from '../preflight';
+
+describe('runPreflight', () => {
+    const ok = () => vi.fn().mockResolvedValue(undefined);
+    const failing = (message: string) => vi.fn().mockRejectedValue(new Error(message));
+
+    it('should be ready when both dependencies are healthy', async () => {
+        const result = await runPreflight({ checkEmbedding: ok(), checkVectorStore: ok() });
+
+        expect(result).toEqual({
+            ready: true,
+            embeddingReady: true,
+            vectorStoreReady: true,
+            problems: [],
+        });
+    });
+
+    it('should retry the embedding check within the retry window', async () => {
+        const checkEmbedding = vi.fn()
+            .mockRejectedValueOnce(new Error('starting'))
+            .mockResolvedValue(undefined);
+
+        const result = await runPreflight(
+            { checkEmbedding, checkVectorStore: ok() },
+            { embeddingRetries: 2, retryDelayMs: 1 }
+        );
+
+        expect(result.ready).toBe(true);
+        expect(checkEmbedding).toHaveBeenCalledTimes(2);
+    });
+
+    it('should report each missing dependency', async () => {
+        const checkEmbedding = failing('ollama down');
+
+        const result = await runPreflight(
+            { checkEmbedding, checkVectorStore: failing('qdrant down') },
+            { embeddingRetries: 1, retryDelayMs: 1 }
+        );
+
+        expect(result.ready).toBe(false);
+        expect(result.embeddingReady).toBe(false);
+        expect(result.vectorStoreReady).toBe(false);
+        expect(result.problems).toHaveLength(2);
+        expect(result.problems[0]).toContain('ollama down');
+        expect(result.problems[1]).toContain('qdrant down');
+        expect(checkEmbedding).toHaveBeenCalledTimes(2);
+    });
+});
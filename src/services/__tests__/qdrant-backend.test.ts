@@ -0,0 +1,451 @@
+/**
+ * QdrantBackend Unit Tests (mocked client)
+ */
+
+import { describe, it, expect, vi } from 'vitest';
+import type { QdrantClient } from '@qdrant/js-client-rest';
+import { QdrantBackend, CollectionNotFoundError, buildQdrantFilter } from '../qdrant-backend';
+import { VECTOR_NAMES } from '../vector-backend';
+import { DEFAULT_QDRANT_TUNING_CONFIG } from '@core/types/setting';
+import { uuidFromKey } from '@utils/uuid';
+
+describe('QdrantBackend', () => {
+    const createBackend = (
+        pointsCount: number | null,
+        exactCount: () => Promise<{ count: number }> = async () => ({ count: 0 }),
+        dimension?: number
+    ) => {
+        const client = {
+            getCollections: vi.fn().mockResolvedValue({ collections: [{ name: 'notes' }] }),
+            getCollection: vi.fn().mockResolvedValue({
+                points_count: pointsCount,
+                config: { params: { vectors: { content_vec: { size: 3 } } } },
+            }),
+            count: vi.fn().mockImplementation(exactCount),
+        };
+        // The lookup-table dimension is a guess; only the probe embedding's length counts
+        const embeddingService = dimension === undefined
+            ? undefined
+            : { getDimension: () => 768, embed: vi.fn().mockResolvedValue(new Array(dimension).fill(0)) };
+        return {
+            client,
+            backend: QdrantBackend.withClient(client as unknown as QdrantClient, 'notes', undefined, embeddingService),
+        };
+    };
+
+    describe('Points Count', () => {
+        it('should fall back to an exact count when points_count is missing', async () => {
+            const { client, backend } = createBackend(null, async () => ({ count: 42 }));
+
+            const health = await backend.getHealth();
+
+            expect(health.pointsCount).toBe(42);
+            expect(client.count).toHaveBeenCalledWith('notes', { exact: true });
+            expect(await backend.count()).toBe(42);
+        });
+
+        it('should report an unknown count when the fallback fails', async () => {
+            const { backend } = createBackend(null, async () => {
+                throw new Error('unavailable');
+            });
+
+            const health = await backend.getHealth();
+
+            expect(health.connected).toBe(true);
+            expect(health.pointsCount).toBeNull();
+        });
+
+        it('should still search when points_count is missing and the exact count fails', async () => {
+            const { client, backend } = createBackend(null, async () => {
+                throw new Error('unavailable');
+            });
+            const query = vi.fn().mockResolvedValue({ points: [] });
+            Object.assign(client, { query });
+
+            await backend.searchWithFusion([1, 0, 0]);
+
+            expect(query).toHaveBeenCalled();
+        });
+
+        it('should skip the search when the collection is empty', async () => {
+            const { client, backend } = createBackend(null, async () => ({ count: 0 }));
+            const query = vi.fn();
+            Object.assign(client, { query });
+
+            expect(await backend.searchWithFusion([1, 0, 0])).toEqual([]);
+            expect(query).not.toHaveBeenCalled();
+        });
+
+        it('should use points_count directly when present', async () => {
+            const { client, backend } = createBackend(7, async () => ({ count: 0 }));
+
+            expect((await backend.getHealth()).pointsCount).toBe(7);
+            expect(client.count).not.toHaveBeenCalled();
+        });
+    });
+
+    describe('Dimension Guard', () => {
+        it('should fail initialization when the model dimension differs from the collection', async () => {
+            const { backend } = createBackend(0, undefined, 4);
+
+            await expect(backend.initialize()).rejects.toThrow(/向量维度为 3.*4 维/);
+        });
+
+        it('should initialize when the dimensions match', async () => {
+            const { backend } = createBackend(0, undefined, 3);
+
+            await expect(backend.initialize()).resolves.toBeUndefined();
+        });
+
+        it('should skip the startup check when the probe embedding fails', async () => {
+            const client = {
+                getCollections: vi.fn().mockResolvedValue({ collections: [{ name: 'notes' }] }),
+                getCollection: vi.fn(),
+            };
+            const embeddingService = { getDimension: () => 4, embed: vi.fn().mockRejectedValue(new Error('offline')) };
+            const backend = QdrantBackend.withClient(client as unknown as QdrantClient, 'notes', undefined, embeddingService);
+
+            await expect(backend.initialize()).resolves.toBeUndefined();
+            expect(client.getCollection).not.toHaveBeenCalled();
+        });
+
+        it('should reject upserts with a different dimension', async () => {
+            const { backend } = createBackend(0);
+            const vector = [0.1, 0.2, 0.3, 0.4];
+
+            await expect(backend.upsertMultiVector({
+                id: 'a',
+                vectors: {
+                    [VECTOR_NAMES.CONTENT]: vector,
+                    [VECTOR_NAMES.SUMMARY]: vector,
+                    [VECTOR_NAMES.TITLE]: vector,
+                },
+                metadata: { filePath: 'a.md' },
+            })).rejects.toThrow(/向量维度/);
+        });
+    });
+
+    describe('Connect', () => {
+        it('should fail on a missing collection without creating it', async () => {
+            const { client, backend } = createBackend(0);
+            client.getCollections.mockResolvedValue({ collections: [] });
+            const createCollection = vi.fn();
+            Object.assign(client, { createCollection });
+
+            await expect(backend.connect()).rejects.toBeInstanceOf(CollectionNotFoundError);
+            await expect(backend.connect()).rejects.toThrow('集合不存在: notes');
+            expect(createCollection).not.toHaveBeenCalled();
+        });
+
+        it('should write to an existing collection without re-creating it', async () => {
+            const { client, backend } = createBackend(0);
+            const upsert = vi.fn().mockResolvedValue({});
+            Object.assign(client, { upsert, createCollection: vi.fn() });
+
+            await backend.connect();
+            await backend.upsertMultiVector({
+                id: 'a',
+                vectors: {
+                    [VECTOR_NAMES.CONTENT]: [1, 0, 0],
+                    [VECTOR_NAMES.SUMMARY]: [1, 0, 0],
+                    [VECTOR_NAMES.TITLE]: [1, 0, 0],
+                },
+                metadata: { filePath: 'a.md' },
+            });
+
+            expect(upsert).toHaveBeenCalledTimes(1);
+            expect(client.getCollection).toHaveBeenCalledTimes(1);
+        });
+    });
+
+    describe('Optimize', () => {
+        it('should re-apply the indexing threshold and report the collection status', async () => {
+            const { client } = createBackend(10);
+            const updateCollection = vi.fn().mockResolvedValue(true);
+            Object.assign(client, { updateCollection });
+            client.getCollection.mockResolvedValue({ status: 'yellow', points_count: 10 });
+            const backend = QdrantBackend.withClient(client as unknown as QdrantClient, 'notes', undefined, undefined, {
+                hnswM: 16,
+                hnswEfConstruct: 100,
+                onDiskPayload: true,
+                indexingThreshold: 2000,
+                optimizeAfterBulkWrites: true,
+            });
+
+            await expect(backend.optimize()).resolves.toBe('yellow');
+            expect(updateCollection).toHaveBeenCalledWith('notes', {
+                optimizers_config: { indexing_threshold: 2000 },
+            });
+        });
+    });
+
+    describe('Multi-Vector', () => {
+        it('should create, fill and query a max-sim multivector when enabled', async () => {
+            const { client, backend } = createBackend(1);
+            const createCollection = vi.fn().mockResolvedValue(true);
+            const upsert = vi.fn().mockResolvedValue({});
+            const query = vi.fn().mockResolvedValue({ points: [] });
+            Object.assign(client, { createCollection, upsert, query, createPayloadIndex: vi.fn() });
+            client.getCollection.mockRejectedValueOnce(new Error('Not found'));
+            backend.setMultiVector(true);
+
+            await backend.upsertMultiVector({
+                id: 'a.md-chunk-0',
+                vectors: {
+                    [VECTOR_NAMES.CONTENT]: [1, 0, 0],
+                    [VECTOR_NAMES.SUMMARY]: [1, 0, 0],
+                    [VECTOR_NAMES.TITLE]: [1, 0, 0],
+                },
+                subVectors: [[1, 0, 0], [0, 1, 0]],
+                metadata: { filePath: 'a.md' },
+            });
+
+            expect(createCollection.mock.calls[0][1].vectors.content_multi_vec).toEqual({
+                size: 3,
+                distance: 'Cosine',
+                multivector_config: { comparator: 'max_sim' },
+            });
+            expect(upsert.mock.calls[0][1].points[0].vector.content_multi_vec).toEqual([[1, 0, 0], [0, 1, 0]]);
+
+            client.getCollection.mockResolvedValue({
+                points_count: 1,
+                config: { params: { vectors: { content_vec: { size: 3 }, content_multi_vec: { size: 3 } } } },
+            });
+            await backend.searchWithFusion([0, 1, 0]);
+
+            const prefetch = query.mock.calls[0][1].prefetch;
+            expect(prefetch).toHaveLength(4);
+            expect(prefetch[3]).toMatchObject({ query: [[0, 1, 0]], using: 'content_multi_vec' });
+        });
+
+        it('should replace sub-vectors when re-embedding and drop them when none are returned', async () => {
+            const { client, backend } = createBackend(2);
+            const updateVectors = vi.fn().mockResolvedValue({});
+            const deleteVectors = vi.fn().mockResolvedValue({});
+            const scroll = vi.fn().mockResolvedValue({
+                points: [
+                    { id: 1, payload: { _customId: 'a.md-chunk-0', content: 'a' } },
+                    { id: 2, payload: { _customId: 'b.md-heading-0', content: 'b', heading_only: true } },
+                ],
+            });
+            Object.assign(client, { scroll, updateVectors, deleteVectors });
+            client.getCollection.mockResolvedValue({
+                config: { params: { vectors: { content_vec: { size: 3 }, content_multi_vec: { size: 3 } } } },
+            });
+            const vector = [0, 0, 1];
+
+            const report = await backend.reembedPoints(async (metadata) => ({
+                vectors: {
+                    [VECTOR_NAMES.CONTENT]: vector,
+                    [VECTOR_NAMES.SUMMARY]: vector,
+                    [VECTOR_NAMES.TITLE]: vector,
+                },
+                ...(!metadata.heading_only && { subVectors: [vector, [0, 1, 0]] }),
+            }));
+
+            expect(report).toEqual({ updated: 2, failed: 0 });
+            const points = updateVectors.mock.calls[0][1].points;
+            expect(points[0].vector.content_multi_vec).toEqual([vector, [0, 1, 0]]);
+            expect(points[1].vector).not.toHaveProperty('content_multi_vec');
+            expect(deleteVectors).toHaveBeenCalledWith('notes', { wait: true, points: [2], vector: ['content_multi_vec'] });
+        });
+    });
+
+    describe('Quantization', () => {
+        it('should create the collection with int8 scalar quantization when enabled', async () => {
+            const { client } = createBackend(0);
+            const createCollection = vi.fn().mockResolvedValue(true);
+            Object.assign(client, { createCollection, createPayloadIndex: vi.fn(), deleteCollection: vi.fn() });
+            client.getCollection.mockRejectedValueOnce(new Error('Not found'));
+            const backend = QdrantBackend.withClient(client as unknown as QdrantClient, 'notes', undefined, undefined, {
+                ...DEFAULT_QDRANT_TUNING_CONFIG,
+                scalarQuantization: true,
+                quantizationQuantile: 0.95,
+            });
+
+            await backend.clear(3);
+
+            expect(createCollection.mock.calls[0][1].quantization_config).toEqual({
+                scalar: { type: 'int8', quantile: 0.95, always_ram: true },
+            });
+        });
+
+        it('should leave quantization off by default', async () => {
+            const { client } = createBackend(0);
+            const createCollection = vi.fn().mockResolvedValue(true);
+            Object.assign(client, { createCollection, createPayloadIndex: vi.fn(), deleteCollection: vi.fn() });
+            client.getCollection.mockRejectedValueOnce(new Error('Not found'));
+            const backend = QdrantBackend.withClient(client as unknown as QdrantClient, 'notes', undefined, undefined, {
+                ...DEFAULT_QDRANT_TUNING_CONFIG,
+            });
+
+            await backend.clear(3);
+
+            expect(createCollection.mock.calls[0][1]).not.toHaveProperty('quantization_config');
+        });
+    });
+
+    describe('Import', () => {
+        const vectors = (vector: number[]) => ({
+            [VECTOR_NAMES.CONTENT]: vector,
+            [VECTOR_NAMES.SUMMARY]: vector,
+            [VECTOR_NAMES.TITLE]: vector,
+        });
+        const conceptId = '6f1c2a9e-3b4d-4e5f-8a7b-9c0d1e2f3a4b';
+        const lines = [
+            JSON.stringify({ id: 'a.md-chunk-0', pointId: 7, metadata: { filePath: 'a.md' }, vectors: vectors([1, 0, 0]) }),
+            JSON.stringify({ id: 'b.md-chunk-0', metadata: { filePath: 'b.md' }, vectors: vectors([0, 1, 0]) }),
+            JSON.stringify({
+                id: conceptId,
+                metadata: { type: 'concept', concept: 'rust' },
+                vectors: { concept_vec: [0, 0, 1], concept_summary_vec: [0, 0, 1] },
+            }),
+            JSON.stringify({ id: 'c.md-chunk-0', metadata: { filePath: 'c.md' }, vectors: vectors([1, 0]) }),
+        ];
+
+        it('should keep point ids so re-importing overwrites instead of duplicating', async () => {
+            const { client, backend } = createBackend(3);
+            const upsert = vi.fn().mockResolvedValue({});
+            Object.assign(client, { upsert, createCollection: vi.fn() });
+            client.getCollection.mockResolvedValue({
+                config: {
+                    params: {
+                        vectors: {
+                            content_vec: { size: 3 },
+                            summary_vec: { size: 3 },
+                            title_vec: { size: 3 },
+                            concept_vec: { size: 3 },
+                            concept_summary_vec: { size: 3 },
+                        },
+                    },
+                },
+            });
+
+            expect(await backend.importPoints(lines)).toEqual({ imported: 3, skipped: 1 });
+            await backend.importPoints(lines);
+
+            const points = upsert.mock.calls[0][1].points;
+            expect(points.map((point: any) => point.id)).toEqual([7, uuidFromKey('b.md-chunk-0'), conceptId]);
+            expect(points[1].payload).toEqual({ filePath: 'b.md', _customId: 'b.md-chunk-0' });
+            expect(points[2]).toEqual({
+                id: conceptId,
+                vector: { concept_vec: [0, 0, 1], concept_summary_vec: [0, 0, 1] },
+                payload: { type: 'concept', concept: 'rust' },
+            });
+            expect(upsert.mock.calls[1][1].points.map((point: any) => point.id)).toEqual(points.map((point: any) => point.id));
+            expect(client.createCollection).not.toHaveBeenCalled();
+        });
+
+        it('should skip lines that do not match an existing collection of another dimension', async () => {
+            const { client, backend } = createBackend(3);
+            const upsert = vi.fn().mockResolvedValue({});
+            Object.assign(client, { upsert, createCollection: vi.fn() });
+
+            const report = await backend.importPoints([lines[3], lines[0]]);
+
+            expect(report).toEqual({ imported: 1, skipped: 1 });
+            expect(upsert.mock.calls[0][1].points.map((point: any) => point.id)).toEqual([7]);
+            expect(client.createCollection).not.toHaveBeenCalled();
+        });
+    });
+
+    describe('Delete', () => {
+        it('should delete one point type by its type filter and report the count', async () => {
+            const { client, backend } = createBackend(10, async () => ({ count: 4 }));
+            const del = vi.fn().mockResolvedValue({});
+            Object.assign(client, { delete: del });
+            const filter = { must: [{ key: 'type', match: { value: 'concept' } }] };
+
+            expect(await backend.deleteByType('concept')).toBe(4);
+
+            expect(client.count).toHaveBeenCalledWith('notes', { filter, exact: true });
+            expect(del).toHaveBeenCalledWith('notes', { filter });
+        });
+
+        it('should delete by a normalized tag and skip the delete when nothing matches', async () => {
+            const { client, backend } = createBackend(10, async () => ({ count: 0 }));
+            const del = vi.fn().mockResolvedValue({});
+            Object.assign(client, { delete: del });
+
+            expect(await backend.deleteByTag(' #rust ')).toBe(0);
+            expect(await backend.deleteByTag('#')).toBe(0);
+
+            expect(client.count).toHaveBeenCalledTimes(1);
+            expect(client.count).toHaveBeenCalledWith('notes', {
+                filter: { must: [{ key: 'tags', match: { value: 'rust' } }] },
+                exact: true,
+            });
+            expect(del).not.toHaveBeenCalled();
+        });
+
+        it('should limit a file delete to one index run or to every other run', async () => {
+            const { client, backend } = createBackend(10);
+            const del = vi.fn().mockResolvedValue({});
+            Object.assign(client, { delete: del });
+            const path = { key: 'filePath', match: { value: 'a.md' } };
+            const run = { key: 'indexRunId', match: { value: 'r1' } };
+
+            await backend.deleteByFilePath('a.md', { indexRunId: 'r1' });
+            await backend.deleteByFilePath('a.md', { exceptIndexRunId: 'r1' });
+
+            expect(del.mock.calls[0][1].filter).toEqual({ must: [path, run] });
+            expect(del.mock.calls[1][1].filter).toEqual({ must: [path], must_not: [run] });
+        });
+    });
+
+    describe('Facets', () => {
+        it('should request exact counts for the field and sort ties by value', async () => {
+            const { client, backend } = createBackend(10);
+            const facet = vi.fn().mockResolvedValue({
+                hits: [{ value: 'rust', count: 3 }, { value: 'go', count: 5 }, { value: 'db', count: 3 }],
+            });
+            Object.assign(client, { facet });
+
+            expect(await backend.facetCounts('tags', 3)).toEqual([
+                { value: 'go', count: 5 },
+                { value: 'db', count: 3 },
+                { value: 'rust', count: 3 },
+            ]);
+            expect(facet).toHaveBeenCalledWith('notes', { key: 'tags', limit: 3, exact: true });
+        });
+    });
+});
+
+describe('buildQdrantFilter', () => {
+    it('should return undefined when nothing is filtered', () => {
+        expect(buildQdrantFilter(undefined)).toBeUndefined();
+        expect(buildQdrantFilter({ tags: [] })).toBeUndefined();
+    });
+
+    it('should AND type, tags and folder in one must clause', () => {
+        expect(buildQdrantFilter({ type: 'chunk', tags: ['rust', 'db'], folder: '/projects/' })).toEqual({
+            must: [
+                { key: 'tags', match: { any: ['rust', 'db'] } },
+                { key: 'folders', match: { value: 'projects' } },
+                { key: 'type', match: { value: 'chunk' } },
+            ],
+        });
+    });
+
+    it('should put an open-ended time range in a single range condition', () => {
+        expect(buildQdrantFilter({ since: 100 })).toEqual({
+            must: [{ key: 'indexedAt', range: { gte: 100, lte: undefined } }],
+        });
+    });
+
+    it('should bound heading depth with an integer range', () => {
+        expect(buildQdrantFilter({ maxDepth: 1 })).toEqual({
+            must: [{ key: 'depth', range: { gte: undefined, lte: 1 } }],
+        });
+    });
+
+    it('should exclude a file with must_not, using the case-folded key when requested', () => {
+        expect(buildQdrantFilter({ excludeFilePath: 'Notes/A.md' })).toEqual({
+            must_not: [{ key: 'filePath', match: { value: 'Notes/A.md' } }],
+        });
+        expect(buildQdrantFilter({ type: 'chunk', excludeFilePath: 'Notes/A.md' }, true)).toEqual({
+            must: [{ key: 'type', match: { value: 'chunk' } }],
+            must_not: [{ key: 'filePathKey', match: { value: 'notes/a.md' } }],
+        });
+    });
+});
@@ -0,0 +1,545 @@
+/**
+ * SearchService Unit Tests
+ */
+
+import { describe, it, expect, beforeEach, vi } from 'vitest';
+import { SearchService, applyCalibration, fitScoreCalibration } from '../search-service';
+import type { EmbeddingService } from '../embedding-service';
+import type { VectorBackend } from '../vector-backend';
+import { VECTOR_NAMES } from '../vector-backend';
+import { MemoryBackend } from '../memory-backend';
+import { VectorIndexManager } from '../vector-index-manager';
+import { Chunker } from '../chunker';
+import { ContentPreprocessor } from '../content-preprocessor';
+import type { MetadataExtractor } from '../metadata-extractor';
+import type { SemanticChunker } from '../semantic-chunker';
+import { DEFAULT_INDEXING_CONFIG } from '@core/types/setting';
+
+const chunk = (id: string, filePath: string, headerPath: string, score: number) => ({
+    id,
+    score,
+    metadata: {
+        filePath,
+        header_path: headerPath,
+        content: `${headerPath} content`,
+    },
+});
+
+describe('SearchService', () => {
+    let searchWithFusion: ReturnType<typeof vi.fn>;
+    let getByFilePath: ReturnType<typeof vi.fn>;
+    let embed: ReturnType<typeof vi.fn>;
+    let searchService: SearchService;
+
+    beforeEach(() => {
+        searchWithFusion = vi.fn().mockResolvedValue([
+            chunk('a1', 'notes/guide.md', '# Guide > ## Install', 0.92),
+            chunk('b1', 'notes/other.md', '# Other', 0.85),
+            chunk('a2', 'notes/guide.md', '# Guide > ## Usage', 0.8),
+            chunk('a3', 'notes/guide.md', '# Guide > ## FAQ', 0.7),
+            chunk('p1', 'projects/alpha/plan.md', '# Plan', 0.65),
+            chunk('p2', 'projects/beta/plan.md', '# Plan', 0.6),
+        ]);
+
+        getByFilePath = vi.fn().mockResolvedValue([
+            { ...chunk('a1', 'notes/guide.md', '# Guide > ## Install', 1), metadata: { filePath: 'notes/guide.md', header_path: '# Guide > ## Install', start_line: 1 } },
+            { ...chunk('a2', 'notes/guide.md', '# Guide > ## Usage', 1), metadata: { filePath: 'notes/guide.md', header_path: '# Guide > ## Usage', start_line: 12 } },
+        ]);
+        embed = vi.fn().mockResolvedValue([0.1, 0.2, 0.3]);
+
+        const embeddingService = { embed } as unknown as EmbeddingService;
+        const vectorBackend = { searchWithFusion, getByFilePath } as unknown as VectorBackend;
+
+        searchService = new SearchService(embeddingService, vectorBackend);
+    });
+
+    describe('Grouped Search', () => {
+        it('should group multiple sections of a note under one path', async () => {
+            const groups = await searchService.searchGrouped('install guide');
+
+            expect(groups).toHaveLength(4);
+            expect(groups[0].notePath).toBe('notes/guide.md');
+            expect(groups[0].topScore).toBe(0.92);
+            expect(groups[0].matches).toHaveLength(3);
+            expect(groups[1].notePath).toBe('notes/other.md');
+            expect(groups[1].matches).toHaveLength(1);
+        });
+
+        it('should over-fetch chunks and limit the number of groups', async () => {
+            const groups = await searchService.searchGrouped('install guide', undefined, 1);
+
+            expect(searchWithFusion).toHaveBeenCalledWith(
+                expect.any(Array),
+                expect.objectContaining({ limit: 5 })
+            );
+            expect(groups).toHaveLength(1);
+            expect(groups[0].notePath).toBe('notes/guide.md');
+        });
+
+        it('should cap the number of groups, not the chunks fetched for them', async () => {
+            const service = new SearchService(
+                { embed } as unknown as EmbeddingService,
+                { searchWithFusion } as unknown as VectorBackend,
+                { maxResults: 2 }
+            );
+
+            const groups = await service.searchGrouped('install guide', undefined, 10);
+
+            expect(searchWithFusion).toHaveBeenCalledWith(
+                expect.any(Array),
+                expect.objectContaining({ limit: 10 })
+            );
+            expect(groups.map(group => group.notePath)).toEqual(['notes/guide.md', 'notes/other.md']);
+            expect(groups[0].matches).toHaveLength(3);
+        });
+    });
+
+    describe('Path Prefix Filter', () => {
+        it('should only return notes under the given folder', async () => {
+            const results = await searchService.search('plan', undefined, 10, {
+                pathPrefix: 'projects/alpha/',
+            });
+
+            expect(results).toHaveLength(1);
+            expect(results[0].notePath).toBe('projects/alpha/plan.md');
+        });
+
+        it('should combine with excludePath', async () => {
+            const results = await searchService.search('plan', 'projects/alpha/plan.md', 10, {
+                pathPrefix: 'projects/',
+            });
+
+            expect(results.map(r => r.notePath)).toEqual(['projects/beta/plan.md']);
+        });
+    });
+
+    describe('Refine', () => {
+        const candidate = (pointId: string, vector: number[]) => ({
+            pointId,
+            notePath: `${pointId}.md`,
+            title: pointId,
+            similarity: 0.5,
+            vector,
+        });
+
+        it('should re-rank only the candidates by cosine similarity to the new query', async () => {
+            embed.mockResolvedValue([1, 0, 0]);
+
+            const results = await searchService.refine('narrower', [
+                candidate('far', [0, 1, 0]),
+                candidate('close', [0.9, 0.1, 0]),
+                candidate('exact', [2, 0, 0]),
+            ]);
+
+            expect(results.map(r => r.pointId)).toEqual(['exact', 'close', 'far']);
+            expect(results[0].similarity).toBeCloseTo(1);
+            expect(results[1].similarity).toBeCloseTo(0.9 / Math.sqrt(0.82));
+            expect(results[2].similarity).toBeCloseTo(0);
+            expect(searchWithFusion).not.toHaveBeenCalled();
+        });
+
+        it('should require stored vectors on every candidate', async () => {
+            await expect(searchService.refine('narrower', [{ notePath: 'a.md', title: 'a', similarity: 1 }]))
+                .rejects.toThrow('withVectors');
+        });
+    });
+
+    describe('Lookup By Path', () => {
+        it('should return all chunks of a note in order without embedding', async () => {
+            embed.mockRejectedValue(new Error('embed must not be called'));
+
+            const results = await searchService.getByPath('notes/guide.md');
+
+            expect(getByFilePath).toHaveBeenCalledWith('notes/guide.md');
+            expect(results.map(r => r.title)).toEqual([
+                '# Guide > ## Install',
+                '# Guide > ## Usage',
+            ]);
+            expect(embed).not.toHaveBeenCalled();
+        });
+    });
+
+    describe('Vectors', () => {
+        it('should only include vectors when requested', async () => {
+            searchWithFusion.mockImplementation(async (_query, options) => [{
+                ...chunk('a1', 'notes/guide.md', '# Guide', 0.9),
+                ...(options.withVectors && { vector: [0.1, 0.2, 0.3] }),
+            }]);
+
+            const withVectors = await searchService.search('guide', undefined, 10, { withVectors: true });
+            const withoutVectors = await searchService.search('guide');
+
+            expect(withVectors[0].vector).toHaveLength(3);
+            expect(withoutVectors[0].vector).toBeUndefined();
+        });
+    });
+
+    describe('Time Range Filter', () => {
+        it('should pass since/until bounds to the backend filter', async () => {
+            const since = Date.UTC(2024, 0, 1);
+            const until = Date.UTC(2024, 0, 8);
+
+            await searchService.search('weekly notes', undefined, 10, { since, until });
+
+            expect(searchWithFusion).toHaveBeenCalledWith(
+                expect.any(Array),
+                expect.objectContaining({ filter: { since, until } })
+            );
+        });
+
+        it('should not filter when no bounds are given', async () => {
+            await searchService.search('weekly notes');
+
+            expect(searchWithFusion).toHaveBeenCalledWith(
+                expect.any(Array),
+                expect.objectContaining({ filter: undefined })
+            );
+        });
+    });
+
+    describe('Minimum Content Length', () => {
+        it('should drop chunks shorter than the threshold', async () => {
+            searchWithFusion.mockResolvedValue([
+                { id: 'short', score: 0.95, metadata: { filePath: 'a.md', content: '标题' } },
+                { id: 'long', score: 0.9, metadata: { filePath: 'b.md', content: '这是一段完整的段落内容。' } },
+            ]);
+
+            const results = await searchService.search('query', undefined, 10, { minContentLength: 5 });
+
+            expect(results.map(r => r.notePath)).toEqual(['b.md']);
+            expect(searchWithFusion).toHaveBeenCalledWith(
+                expect.any(Array),
+                expect.objectContaining({ limit: 40 })
+            );
+        });
+    });
+
+    describe('Debug', () => {
+        beforeEach(() => {
+            searchWithFusion.mockResolvedValue([
+                { id: 'p1', score: 0.8, metadata: { filePath: 'a.md', content: 'alpha' } },
+                { id: 'p2', score: 0.6, metadata: { filePath: 'b.md', content: 'beta' } },
+            ]);
+        });
+
+        it('should attach score, rank and filters when requested', async () => {
+            const results = await searchService.search('query', 'a.md', 10, { debug: true, since: 1000 });
+
+            expect(results).toHaveLength(1);
+            expect(results[0].debug).toEqual({
+                pointId: 'p2',
+                rawScore: 0.6,
+                backendRank: 2,
+                filters: {
+                    excludePath: 'a.md',
+                    pathPrefix: undefined,
+                    since: 1000,
+                    until: undefined,
+                    minContentLength: undefined,
+                },
+            });
+        });
+
+        it('should omit debug info by default', async () => {
+            const results = await searchService.search('query');
+
+            expect(results.every(r => r.debug === undefined)).toBe(true);
+        });
+    });
+
+    describe('Search By Vectors', () => {
+        const point = (id: string, vector: number[]) => ({
+            id,
+            vectors: {
+                [VECTOR_NAMES.CONTENT]: vector,
+                [VECTOR_NAMES.SUMMARY]: vector,
+                [VECTOR_NAMES.TITLE]: vector,
+            },
+            metadata: { filePath: `${id}.md`, content: id },
+        });
+
+        it('should return neighbors of the liked vectors, excluding the sources', async () => {
+            const backend = new MemoryBackend();
+            await backend.upsertMultiVector(point('a1', [1, 0.1, 0]));
+            await backend.upsertMultiVector(point('a2', [0.9, 0.2, 0]));
+            await backend.upsertMultiVector(point('a3', [1, 0, 0.1]));
+            await backend.upsertMultiVector(point('b1', [0, 1, 0]));
+            await backend.upsertMultiVector(point('b2', [0, 0.1, 1]));
+            const service = new SearchService({ embed } as unknown as EmbeddingService, backend);
+
+            const results = await service.searchByVectors(
+                [[1, 0.1, 0], [0.9, 0.2, 0]],
+                2,
+                ['a1', 'a2']
+            );
+
+            expect(results.map(r => r.pointId)).toEqual(['a3', 'b1']);
+            expect(embed).not.toHaveBeenCalled();
+        });
+
+        it('should return nothing for an empty selection', async () => {
+            expect(await searchService.searchByVectors([])).toEqual([]);
+            expect(searchWithFusion).not.toHaveBeenCalled();
+        });
+    });
+
+    describe('Score Transform', () => {
+        it('should map the best score to 1 and the worst to 0 with minmax', async () => {
+            const results = await searchService.search('query', undefined, 10, { scoreTransform: 'minmax' });
+
+            expect(results[0].similarity).toBe(1);
+            expect(results[results.length - 1].similarity).toBe(0);
+            expect(results.map(r => r.rawScore)).toEqual([0.92, 0.85, 0.8, 0.7, 0.65, 0.6]);
+        });
+
+        it('should use the configured transform by default', async () => {
+            const service = new SearchService(
+                { embed } as unknown as EmbeddingService,
+                { searchWithFusion } as unknown as VectorBackend,
+                { scoreTransform: 'rank' }
+            );
+
+            const results = await service.search('query', undefined, 4);
+
+            expect(results.map(r => r.similarity)).toEqual([1, 0.75, 0.5, 0.25]);
+            expect(results[0].rawScore).toBe(0.92);
+        });
+    });
+
+    describe('Recency Boost', () => {
+        const day = 24 * 60 * 60 * 1000;
+
+        beforeEach(() => {
+            const now = Date.now();
+            searchWithFusion.mockResolvedValue([
+                { ...chunk('old', 'notes/old.md', '# Old', 0.8), metadata: { filePath: 'notes/old.md', indexedAt: now - 90 * day } },
+                { ...chunk('new', 'notes/new.md', '# New', 0.8), metadata: { filePath: 'notes/new.md', indexedAt: now - day } },
+            ]);
+        });
+
+        it('should rank the newer of two equally relevant chunks first', async () => {
+            const results = await searchService.search('query', undefined, 10, { recencyBoost: 0.1 });
+
+            expect(results.map(r => r.notePath)).toEqual(['notes/new.md', 'notes/old.md']);
+            expect(results[0].similarity).toBeGreaterThan(results[1].similarity);
+            expect(results.map(r => r.rawScore)).toEqual([0.8, 0.8]);
+        });
+
+        it('should not let a small boost outweigh a clearly better match', async () => {
+            const now = Date.now();
+            // RRF-sized scores: the old chunk matches twice as well
+            searchWithFusion.mockResolvedValue([
+                { ...chunk('old', 'notes/old.md', '# Old', 0.03), metadata: { filePath: 'notes/old.md', indexedAt: now - 90 * day } },
+                { ...chunk('new', 'notes/new.md', '# New', 0.015), metadata: { filePath: 'notes/new.md', indexedAt: now } },
+            ]);
+
+            const results = await searchService.search('query', undefined, 10, { recencyBoost: 0.1 });
+
+            expect(results.map(r => r.notePath)).toEqual(['notes/old.md', 'notes/new.md']);
+            expect(results.map(r => r.rawScore)).toEqual([0.03, 0.015]);
+        });
+
+        it('should calibrate the raw score and add the boost afterwards', async () => {
+            searchService.updateConfig({ calibration: { slope: 10, intercept: -8 } });
+
+            const results = await searchService.search('query', undefined, 10, { recencyBoost: 0.1, calibrated: true });
+
+            expect(results.map(r => r.notePath)).toEqual(['notes/new.md', 'notes/old.md']);
+            expect(results[0].similarity).toBeCloseTo(0.5 + 0.1 * Math.pow(0.5, 1 / 30), 3);
+            expect(results[1].similarity).toBeCloseTo(0.5 + 0.1 * Math.pow(0.5, 3), 3);
+        });
+
+        it('should keep backend order when disabled', async () => {
+            const results = await searchService.search('query');
+
+            expect(results.map(r => r.notePath)).toEqual(['notes/old.md', 'notes/new.md']);
+            expect(results.map(r => r.similarity)).toEqual([0.8, 0.8]);
+        });
+    });
+
+    describe('Default Point Type', () => {
+        const typed = (id: string, type: string) => ({
+            id,
+            vectors: {
+                [VECTOR_NAMES.CONTENT]: [1, 0, 0],
+                [VECTOR_NAMES.SUMMARY]: [1, 0, 0],
+                [VECTOR_NAMES.TITLE]: [1, 0, 0],
+            },
+            metadata: { filePath: `${id}.md`, content: id, type },
+        });
+
+        it('should only return the default type unless a query overrides it', async () => {
+            const backend = new MemoryBackend();
+            await backend.upsertMultiVector(typed('c1', 'chunk'));
+            await backend.upsertMultiVector(typed('k1', 'concept'));
+            await backend.upsertMultiVector(typed('c2', 'chunk'));
+            const service = new SearchService(
+                { embed: vi.fn().mockResolvedValue([1, 0, 0]) } as unknown as EmbeddingService,
+                backend,
+                { defaultPointType: 'chunk' }
+            );
+
+            const results = await service.search('query');
+            const concepts = await service.search('query', undefined, 10, { type: 'concept' });
+
+            expect(results.map(r => r.pointId).sort()).toEqual(['c1', 'c2']);
+            expect(concepts.map(r => r.pointId)).toEqual(['k1']);
+        });
+
+        it('should order equally scored types by their weights', async () => {
+            const backend = new MemoryBackend();
+            await backend.upsertMultiVector(typed('c1', 'chunk'));
+            await backend.upsertMultiVector(typed('k1', 'concept'));
+            const service = new SearchService(
+                { embed: vi.fn().mockResolvedValue([1, 0, 0]) } as unknown as EmbeddingService,
+                backend
+            );
+
+            const chunkFirst = await service.search('query', undefined, 10, { typeWeights: { concept: 0.5 } });
+            const conceptFirst = await service.search('query', undefined, 10, { typeWeights: { chunk: 0.5 } });
+
+            expect(chunkFirst.map(r => r.pointId)).toEqual(['c1', 'k1']);
+            expect(chunkFirst[1].similarity).toBeCloseTo(chunkFirst[1].rawScore! * 0.5);
+            expect(conceptFirst.map(r => r.pointId)).toEqual(['k1', 'c1']);
+        });
+    });
+
+    describe('Heading Depth', () => {
+        it('should return only top-level sections with maxDepth 1', async () => {
+            const backend = new MemoryBackend();
+            const embeddingService = { embed: vi.fn().mockResolvedValue([1, 0, 0]) } as unknown as EmbeddingService;
+            const manager = new VectorIndexManager(
+                backend,
+                embeddingService,
+                new Chunker(500),
+                {
+                    extract: vi.fn().mockResolvedValue({ summary: '', tags: [], category: '', concepts: [] }),
+                } as unknown as MetadataExtractor,
+                new ContentPreprocessor(),
+                {} as unknown as SemanticChunker,
+                1024 * 1024,
+                { ...DEFAULT_INDEXING_CONFIG },
+            );
+            await manager.indexFile('doc.md', '# Top\nintro\n## Middle\ndetail\n### Deep\nmore detail\n');
+            await manager.flush();
+            manager.stop();
+            const service = new SearchService(embeddingService, backend);
+
+            const topLevel = await service.search('detail', undefined, 10, { maxDepth: 1 });
+            const deeper = await service.search('detail', undefined, 10, { minDepth: 2 });
+
+            expect(topLevel.map(r => r.title)).toEqual(['# Top']);
+            expect(deeper.map(r => r.title).sort()).toEqual(['# Top > ## Middle', '# Top > ## Middle > ### Deep']);
+        });
+    });
+
+    describe('Empty Query', () => {
+        it('should return nothing without embedding for empty or blank queries', async () => {
+            embed.mockImplementation(async () => {
+                throw new Error('embed must not be called');
+            });
+
+            expect(await searchService.search('')).toEqual([]);
+            expect(await searchService.search(' \n\t ')).toEqual([]);
+            expect(await searchService.searchGrouped('   ')).toEqual([]);
+            expect(embed).not.toHaveBeenCalled();
+            expect(searchWithFusion).not.toHaveBeenCalled();
+        });
+
+        it('should trim the query and cap the limit', async () => {
+            const service = new SearchService(
+                { embed } as unknown as EmbeddingService,
+                { searchWithFusion } as unknown as VectorBackend,
+                { maxResults: 2 }
+            );
+
+            const results = await service.search('  install guide  ', undefined, 10);
+
+            expect(embed).toHaveBeenCalledWith('install guide');
+            expect(results).toHaveLength(2);
+        });
+    });
+
+    describe('Weak Queries', () => {
+        const createService = (weakQueryBehavior: 'empty' | 'widen') => new SearchService(
+            { embed } as unknown as EmbeddingService,
+            { searchWithFusion } as unknown as VectorBackend,
+            { weakQueryBehavior, minQueryTokens: 1 }
+        );
+
+        it('should search stop-word-only queries when the check is disabled by default', async () => {
+            const service = new SearchService(
+                { embed } as unknown as EmbeddingService,
+                { searchWithFusion } as unknown as VectorBackend
+            );
+
+            const results = await service.search('the', undefined, 2);
+
+            expect(embed).toHaveBeenCalledWith('the');
+            expect(results).toHaveLength(2);
+            expect(results[0].weakQuery).toBeUndefined();
+        });
+
+        it('should return nothing for stop-word-only queries when configured', async () => {
+            const service = createService('empty');
+
+            expect(await service.search('the')).toEqual([]);
+            expect(await service.search('的')).toEqual([]);
+            expect(embed).not.toHaveBeenCalled();
+        });
+
+        it('should widen the limit and flag results when configured', async () => {
+            const service = createService('widen');
+
+            const results = await service.search('of the', undefined, 2);
+
+            expect(searchWithFusion).toHaveBeenCalledWith(expect.anything(), expect.objectContaining({ limit: 6 }));
+            expect(results).toHaveLength(6);
+            expect(results.every(r => r.weakQuery)).toBe(true);
+        });
+
+        it('should leave normal queries unaffected', async () => {
+            const results = await createService('empty').search('the install guide', undefined, 2);
+
+            expect(results).toHaveLength(2);
+            expect(results[0].weakQuery).toBeUndefined();
+        });
+    });
+
+    describe('Calibration', () => {
+        it('should map raw scores monotonically into [0, 1]', () => {
+            const calibration = fitScoreCalibration([0.9, 0.85, 0.8], [0.5, 0.4, 0.45]);
+            const raw = [-1, 0, 0.3, 0.45, 0.6, 0.65, 0.85, 1, 2];
+            const mapped = raw.map(score => applyCalibration(score, calibration));
+
+            expect(mapped.every(p => p >= 0 && p <= 1)).toBe(true);
+            expect(mapped.every((p, i) => i === 0 || p > mapped[i - 1])).toBe(true);
+            expect(applyCalibration(0.85, calibration)).toBeGreaterThan(0.8);
+            expect(applyCalibration(0.45, calibration)).toBeLessThan(0.2);
+            expect(applyCalibration(0.65, calibration)).toBeCloseTo(0.5);
+        });
+
+        it('should refuse scores where relevant results do not rank higher', () => {
+            expect(() => fitScoreCalibration([0.4], [0.6])).toThrow();
+            expect(() => fitScoreCalibration([0.9], [])).toThrow();
+        });
+
+        it('should fit from known relevant notes and apply to calibrated searches', async () => {
+            const calibration = await searchService.calibrate([
+                { query: 'install guide', relevantPath: 'notes/guide.md' },
+            ]);
+
+            expect(calibration.slope).toBeGreaterThan(0);
+            searchService.updateConfig({ calibration });
+            const results = await searchService.search('install guide', undefined, 10, { calibrated: true });
+            expect(results.map(r => r.rawScore)).toEqual([0.92, 0.85, 0.8, 0.7, 0.65, 0.6]);
+            expect(results.every(r => r.similarity > 0 && r.similarity < 1)).toBe(true);
+            expect(results[0].similarity).toBeGreaterThan(results[results.length - 1].similarity);
+        });
+
+        it('should throw for calibrated searches before calibrating', async () => {
+            await expect(searchService.search('install guide', undefined, 10, { calibrated: true }))
+                .rejects.toThrow('not calibrated');
+        });
+    });
+});
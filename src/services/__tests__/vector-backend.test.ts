@@ -0,0 +1,39 @@
+/**
+ * VectorBackend Helpers Unit Tests
+ */
+
+import { describe, it, expect } from 'vitest';
+import { compareSearchResults } from '../vector-backend';
+import type { SearchResult } from '../vector-backend';
+
+describe('compareSearchResults', () => {
+    const result = (id: string, score: number, filePath: string, startLine: number): SearchResult => ({
+        id,
+        score,
+        metadata: { filePath, start_line: startLine, content: 'duplicated content' },
+    });
+
+    it('should break score ties by path, start line, then id', () => {
+        const results = [
+            result('c', 0.8, 'b.md', 1),
+            result('b', 0.8, 'a.md', 10),
+            result('z', 0.9, 'z.md', 1),
+            result('a', 0.8, 'a.md', 10),
+            result('d', 0.8, 'a.md', 2),
+        ];
+
+        const ids = [...results].sort(compareSearchResults).map(r => r.id);
+
+        expect(ids).toEqual(['z', 'd', 'a', 'b', 'c']);
+    });
+
+    it('should give the same order regardless of input order', () => {
+        const first = result('x', 0.5, 'same.md', 3);
+        const second = result('y', 0.5, 'same.md', 3);
+
+        const forward = [first, second].sort(compareSearchResults).map(r => r.id);
+        const reversed = [second, first].sort(compareSearchResults).map(r => r.id);
+
+        expect(forward).toEqual(reversed);
+    });
+});
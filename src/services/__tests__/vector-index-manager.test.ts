@@ -0,0 +1,417 @@
+/**
+ * VectorIndexManager Unit Tests
+ */
+
+import { describe, it, expect, beforeEach, afterEach, vi } from 'vitest';
+import { VectorIndexManager, IndexTimeoutError } from '../vector-index-manager';
+import { Chunker } from '../chunker';
+import { MemoryBackend } from '../memory-backend';
+import { ContentPreprocessor } from '../content-preprocessor';
+import type { EmbeddingService } from '../embedding-service';
+import type { MetadataExtractor } from '../metadata-extractor';
+import type { SemanticChunker } from '../semantic-chunker';
+import type { VectorBackend } from '../vector-backend';
+import type { IndexingConfig } from '@core/types/setting';
+import { DEFAULT_INDEXING_CONFIG } from '@core/types/setting';
+
+describe('VectorIndexManager', () => {
+    let embed: ReturnType<typeof vi.fn>;
+    let renameFilePath: ReturnType<typeof vi.fn>;
+    let upsertMultiVector: ReturnType<typeof vi.fn>;
+    let deleteByFilePath: ReturnType<typeof vi.fn>;
+    let manager: VectorIndexManager;
+
+    const createManager = (
+        config: Partial<IndexingConfig> = {},
+        chunker: Chunker = new Chunker(500),
+        backend?: VectorBackend,
+    ) => new VectorIndexManager(
+        backend ?? {
+            upsertMultiVector,
+            renameFilePath,
+            deleteByFilePath,
+            searchWithFusion: vi.fn().mockResolvedValue([]),
+            // Reports the contentHash of the last point upserted for the path
+            getPathInfo: vi.fn(async (filePath: string) => {
+                const points = upsertMultiVector.mock.calls
+                    .map(([point]) => point.metadata)
+                    .filter((metadata) => metadata.filePath === filePath);
+                return points.length > 0
+                    ? { chunks: points.length, latestIndexedAt: null, contentHash: points[points.length - 1].contentHash }
+                    : null;
+            }),
+        } as unknown as VectorBackend,
+        { embed } as unknown as EmbeddingService,
+        chunker,
+        {
+            extract: vi.fn().mockResolvedValue({
+                summary: 'summary',
+                tags: [],
+                category: '',
+                concepts: [],
+            }),
+        } as unknown as MetadataExtractor,
+        new ContentPreprocessor(),
+        {} as unknown as SemanticChunker,
+        1024 * 1024,
+        { ...DEFAULT_INDEXING_CONFIG, ...config },
+    );
+
+    beforeEach(() => {
+        embed = vi.fn().mockResolvedValue([0.1, 0.2, 0.3]);
+        renameFilePath = vi.fn().mockResolvedValue(1);
+        upsertMultiVector = vi.fn().mockResolvedValue(undefined);
+        deleteByFilePath = vi.fn().mockResolvedValue(undefined);
+    });
+
+    afterEach(() => {
+        manager?.stop();
+    });
+
+    describe('Embedding Input', () => {
+        it('should prepend the header path when enabled', async () => {
+            manager = createManager({ embedWithHeaderPath: true });
+
+            await manager.indexFile('note.md', '# Title\nBody text');
+
+            expect(embed).toHaveBeenCalledWith('# Title\n# Title\nBody text');
+            const cached = manager.getFromCache('note.md-chunk-0');
+            expect(cached?.metadata.content).toBe('# Title\nBody text');
+            expect(cached?.metadata.title).toBe('Title');
+        });
+
+        it('should embed only the content when disabled', async () => {
+            manager = createManager();
+
+            await manager.indexFile('note.md', '# Title\nBody text');
+
+            expect(embed).toHaveBeenCalledWith('# Title\nBody text');
+            expect(embed).not.toHaveBeenCalledWith('# Title\n# Title\nBody text');
+        });
+
+        it('should strip markdown noise from the embedder input only', async () => {
+            manager = createManager({ stripMarkdown: true });
+            const content = 'Some **bold** and ==marked== [link](https://example.com) <!-- note -->';
+
+            await manager.indexFile('note.md', content);
+
+            expect(embed).toHaveBeenCalledWith('Some bold and marked link');
+            expect(manager.getFromCache('note.md-chunk-0')?.metadata.content).toBe(content);
+        });
+    });
+
+    describe('Batch Indexing', () => {
+        it('should report per-file failures without aborting the batch', async () => {
+            embed.mockImplementation(async (text: string) => {
+                if (text.includes('broken')) {
+                    throw new Error('embedding failed');
+                }
+                return [0.1, 0.2, 0.3];
+            });
+            manager = createManager();
+
+            const report = await manager.indexFiles([
+                { filePath: 'a.md', content: '# A\nfirst' },
+                { filePath: 'b.md', content: '# B\nbroken' },
+                { filePath: 'c.md', content: '# C\nthird' },
+            ]);
+
+            expect(report.indexed).toBe(2);
+            expect(report.failed).toBe(1);
+            expect(report.totalChunks).toBe(2);
+            expect(report.files.map(f => f.success)).toEqual([true, false, true]);
+            expect(report.files[1].error).toContain('embedding failed');
+        });
+
+        it('should index many files with bounded concurrency and flush them all', async () => {
+            let inFlight = 0;
+            let maxInFlight = 0;
+            // Only the content embedding carries the body, one per file in flight
+            embed.mockImplementation(async (text: string) => {
+                if (!text.includes('body of')) {
+                    return [0.1, 0.2, 0.3];
+                }
+                inFlight++;
+                maxInFlight = Math.max(maxInFlight, inFlight);
+                await new Promise(resolve => setTimeout(resolve, 1));
+                inFlight--;
+                return [0.1, 0.2, 0.3];
+            });
+            const read = vi.fn(async (path: string) => `# ${path}\nbody of ${path}`);
+            const paths = Array.from({ length: 40 }, (_, i) => `notes/${i}.md`);
+            const progress: number[] = [];
+            manager = createManager({ indexConcurrency: 3, persistBatchSize: 8 });
+
+            const report = await manager.indexPaths(paths, read, p => progress.push(p.done));
+
+            expect(maxInFlight).toBeGreaterThan(1);
+            expect(maxInFlight).toBeLessThanOrEqual(3);
+            expect(read).toHaveBeenCalledTimes(40);
+            expect(report.indexed).toBe(40);
+            expect(progress).toEqual(Array.from({ length: 40 }, (_, i) => i + 1));
+            expect(manager.getQueueSize()).toBe(0);
+            const upserted = new Set(upsertMultiVector.mock.calls.map(([item]) => item.metadata.filePath));
+            expect(upserted.size).toBe(40);
+        });
+    });
+
+    describe('Oversized Chunks', () => {
+        const blob = 'A'.repeat(1200);
+        const content = `intro text\n${blob}\noutro text\n`;
+
+        it('should drop an unbreakable line with the skip policy and report it', async () => {
+            manager = createManager({ oversizedPolicy: 'skip' }, new Chunker(100, { oversizedPolicy: 'skip' }));
+
+            const report = await manager.indexFiles([{ filePath: 'blob.md', content }]);
+
+            expect(report.files[0]).toMatchObject({ success: true, chunks: 2, oversized: 1 });
+            expect(embed.mock.calls.some(([text]) => text.includes('AAAA'))).toBe(false);
+        });
+
+        it('should store a placeholder with the placeholder policy', async () => {
+            manager = createManager(
+                { oversizedPolicy: 'placeholder' },
+                new Chunker(100, { oversizedPolicy: 'placeholder' }),
+            );
+
+            const report = await manager.indexFiles([{ filePath: 'blob.md', content }]);
+
+            expect(report.files[0]).toMatchObject({ chunks: 3, oversized: 1 });
+            expect(manager.getFromCache('blob.md-chunk-1')?.metadata.content).toBe('[omitted binary blob 1200 chars]');
+            expect(embed).toHaveBeenCalledWith('[omitted binary blob 1200 chars]');
+        });
+    });
+
+    describe('Re-indexing', () => {
+        it('should replace the previous points of a file instead of adding to them', async () => {
+            const backend = new MemoryBackend();
+            manager = createManager({}, undefined, backend);
+
+            await manager.indexPaths(['note.md'], async () => '# A\none\n# B\ntwo\n# C\nthree\n');
+            await manager.indexPaths(['note.md'], async () => '# A\none\n# B\nchanged\n');
+            await manager.updateFile('note.md', '# A\none\n# B\nchanged again\n');
+
+            const points = await backend.getByFilePath('note.md');
+            expect(points.map(p => p.metadata.content.trim())).toEqual(['# A\none', '# B\nchanged again']);
+            expect(await backend.count()).toBe(2);
+        });
+
+        it('should delete earlier runs only after the new chunks are stored', async () => {
+            manager = createManager();
+
+            await manager.indexFile('note.md', '# A\none\n');
+
+            const [point] = upsertMultiVector.mock.calls[0];
+            expect(deleteByFilePath).toHaveBeenCalledWith('note.md', { exceptIndexRunId: point.metadata.indexRunId });
+            expect(upsertMultiVector.mock.invocationCallOrder[0])
+                .toBeLessThan(deleteByFilePath.mock.invocationCallOrder[0]);
+            expect(manager.getQueueSize()).toBe(0);
+        });
+    });
+
+    describe('Unchanged Files', () => {
+        it('should skip re-embedding a file whose content is unchanged', async () => {
+            manager = createManager();
+            let content = '# Note\nfirst version';
+            const read = async () => content;
+
+            await manager.indexPaths(['note.md'], read);
+            embed.mockClear();
+
+            const unchanged = await manager.indexPaths(['note.md'], read);
+            expect(embed).not.toHaveBeenCalled();
+            expect(unchanged.unchanged).toBe(1);
+            expect(unchanged.files[0]).toMatchObject({ success: true, unchanged: true, chunks: 0 });
+
+            content = '# Note\nsecond version';
+            const changed = await manager.indexPaths(['note.md'], read);
+            expect(embed).toHaveBeenCalledWith('# Note\nsecond version');
+            expect(changed.unchanged).toBe(0);
+            expect(changed.files[0].unchanged).toBeUndefined();
+        });
+
+        it('should re-index when a chunk setting changes', async () => {
+            manager = createManager();
+            const read = async () => '# Note\nbody';
+
+            await manager.indexPaths(['note.md'], read);
+            embed.mockClear();
+            manager.updateConfig({ embedWithHeaderPath: true });
+
+            const report = await manager.indexPaths(['note.md'], read);
+            expect(report.unchanged).toBe(0);
+            expect(embed).toHaveBeenCalled();
+        });
+    });
+
+    describe('Rename', () => {
+        it('should move indexed chunks to the new path without re-embedding', async () => {
+            manager = createManager();
+            await manager.indexFile('old/note.md', '# Title\nBody text');
+            embed.mockClear();
+
+            const updated = await manager.renameFile('old/note.md', 'new/note.md');
+
+            expect(updated).toBe(1);
+            expect(renameFilePath).toHaveBeenCalledWith('old/note.md', 'new/note.md');
+            expect(manager.getFromCache('old/note.md-chunk-0')?.metadata.filePath).toBe('new/note.md');
+            expect(embed).not.toHaveBeenCalled();
+        });
+
+        it('should recompute the case-folded path key of cached chunks', async () => {
+            manager = createManager({ caseInsensitivePaths: true });
+            await manager.indexFile('old/note.md', '# Title\nBody text');
+
+            await manager.renameFile('old/note.md', 'Archive/Note.md');
+
+            expect(manager.getFromCache('old/note.md-chunk-0')?.metadata).toMatchObject({
+                filePath: 'Archive/Note.md',
+                filePathKey: 'archive/note.md',
+                folders: ['Archive'],
+            });
+        });
+    });
+
+    describe('Progress', () => {
+        it('should report monotonically increasing progress up to the total', async () => {
+            manager = createManager();
+            const progress: Array<{ embedded: number; total: number }> = [];
+
+            const total = await manager.indexFile(
+                'note.md',
+                '# A\none\n# B\ntwo\n# C\nthree\n',
+                (p) => progress.push(p),
+            );
+
+            expect(total).toBe(3);
+            expect(progress.map(p => p.embedded)).toEqual([1, 2, 3]);
+            expect(progress.every(p => p.total === 3)).toBe(true);
+        });
+    });
+
+    describe('Empty Chunks', () => {
+        it('should skip chunks with nothing left to embed and report how many', async () => {
+            manager = createManager(
+                { stripMarkdown: true },
+                new Chunker(500, { includeHeadingInContent: false }),
+            );
+
+            const report = await manager.indexFiles([{
+                filePath: 'note.md',
+                content: '# Draft\n<!-- todo -->\n# Real\nBody text\n',
+            }]);
+
+            expect(report.files[0].chunks).toBe(1);
+            expect(report.files[0].skipped).toBe(1);
+            expect(embed).not.toHaveBeenCalledWith('');
+            expect(manager.getCacheSize()).toBe(1);
+        });
+
+        it('should report the skip count when no chunk is left', async () => {
+            manager = createManager(
+                { stripMarkdown: true },
+                new Chunker(500, { includeHeadingInContent: false }),
+            );
+            const progress: Array<{ total: number; skipped: number }> = [];
+
+            const total = await manager.indexFile('note.md', '# Draft\n<!-- todo -->\n', (p) => progress.push(p));
+
+            expect(total).toBe(0);
+            expect(progress).toEqual([{ embedded: 0, total: 0, capped: false, skipped: 1, oversized: 0 }]);
+            expect(upsertMultiVector).not.toHaveBeenCalled();
+            expect(manager.getCacheSize()).toBe(0);
+        });
+    });
+
+    describe('Timeout', () => {
+        it('should abort a slow index and leave no partial chunks behind', async () => {
+            embed.mockImplementation(
+                () => new Promise(resolve => setTimeout(() => resolve([0.1, 0.2, 0.3]), 50))
+            );
+            manager = createManager({ indexTimeoutMs: 20 });
+
+            await expect(
+                manager.indexFile('slow.md', '# A\none\n# B\ntwo\n')
+            ).rejects.toBeInstanceOf(IndexTimeoutError);
+
+            // Let the in-flight embedding settle before checking for leftovers
+            await new Promise(resolve => setTimeout(resolve, 100));
+            expect(manager.getCacheSize()).toBe(0);
+            expect(manager.getQueueSize()).toBe(0);
+            expect(deleteByFilePath).toHaveBeenCalledWith('slow.md', { indexRunId: expect.any(String) });
+        });
+
+        it('should keep the previous index when a re-index times out', async () => {
+            const backend = new MemoryBackend();
+            manager = createManager({ indexTimeoutMs: 50, persistBatchSize: 1 }, undefined, backend);
+            await manager.indexFile('slow.md', '# A\none\n');
+            await manager.flush();
+
+            // The first chunk is flushed before the second one stalls
+            embed.mockImplementation((text: string) => text.includes('two')
+                ? new Promise(resolve => setTimeout(() => resolve([0.1, 0.2, 0.3]), 200))
+                : Promise.resolve([0.1, 0.2, 0.3])
+            );
+            await expect(
+                manager.indexFile('slow.md', '# A\none more\n# B\ntwo\n')
+            ).rejects.toBeInstanceOf(IndexTimeoutError);
+
+            await new Promise(resolve => setTimeout(resolve, 250));
+            const points = await backend.getByFilePath('slow.md');
+            expect(points.map(p => p.metadata.content.trim())).toEqual(['# A\none']);
+        });
+    });
+
+    describe('Heading Points', () => {
+        it('should index heading text as its own point and rank it for a title query', async () => {
+            embed.mockImplementation(async (text: string) =>
+                text === 'Setup Guide' ? [1, 0, 0] : [0, 1, 0]
+            );
+            manager = createManager({ indexHeadings: true });
+
+            await manager.indexFile('note.md', '# Setup Guide\nInstall the package and run the command.\n');
+
+            const heading = manager.getFromCache('note.md-heading-0');
+            expect(heading?.metadata.content).toBe('Setup Guide');
+            expect(heading?.metadata.header_path).toBe('# Setup Guide');
+            expect(heading?.metadata.heading_only).toBe(true);
+
+            const results = await manager.search('Setup Guide');
+            expect(results[0].id).toBe('note.md-heading-0');
+        });
+
+        it('should skip headings that dominate a tiny chunk', async () => {
+            manager = createManager({ indexHeadings: true });
+
+            await manager.indexFile('note.md', '# Setup Guide\nShort\n');
+
+            expect(manager.getFromCache('note.md-heading-0')).toBeUndefined();
+            expect(manager.getCacheSize()).toBe(1);
+        });
+    });
+
+    describe('Chunk Cap', () => {
+        const content = '# A\none\n# B\ntwo\n# C\nthree\n# D\nfour\n';
+
+        it('should truncate chunks past the cap and flag it', async () => {
+            manager = createManager({ maxChunksPerDoc: 2, chunkOverflowPolicy: 'truncate' });
+
+            const report = await manager.indexFiles([{ filePath: 'big.md', content }]);
+
+            expect(report.files[0].chunks).toBe(2);
+            expect(report.files[0].capped).toBe(true);
+            expect(manager.getFromCache('big.md-chunk-2')).toBeUndefined();
+        });
+
+        it('should merge the tail into the last allowed chunk', async () => {
+            manager = createManager({ maxChunksPerDoc: 2, chunkOverflowPolicy: 'merge' });
+            const progress: boolean[] = [];
+
+            const total = await manager.indexFile('big.md', content, (p) => progress.push(p.capped));
+
+            expect(total).toBe(2);
+            expect(progress).toEqual([true, true]);
+            expect(manager.getFromCache('big.md-chunk-1')?.metadata.content).toBe('# B\ntwo\n# C\nthree\n# D\nfour\n');
+        });
+    });
+});
@@ -3,33 +3,124 @@
  * Ported from Rust implementation
  */
 
-import type { Header, ChunkResult } from '@core/types/indexing';
+import type {
+    Header,
+    ChunkResult,
+    ChunkReport,
+    ChunkerOptions,
+    ChunkStrategy,
+    ChunkStrategyName,
+    EmptySectionBehavior,
+    OversizedPolicy,
+} from '@core/types/indexing';
+import { normalizeNewlines } from '@utils/text-utils';
+
+export type {
+    Header,
+    ChunkResult,
+    ChunkReport,
+    ChunkerOptions,
+    ChunkStrategy,
+    ChunkStrategyName,
+    EmptySectionBehavior,
+    OversizedPolicy,
+};
+
+// A thematic break: three or more of the same `-`, `*` or `_`, optionally spaced
+const HORIZONTAL_RULE = /^ {0,3}([-*_])(?:[ \t]*\1){2,}[ \t]*$/;
+
+// A line without any whitespace, e.g. a base64 blob or a minified payload
+const UNBREAKABLE_LINE = /^\S+\n?$/;
+
+// First line of an Obsidian callout: `> [!type]`, optional fold marker and title
+const CALLOUT_START = /^ {0,3}>[ \t]*\[!([\w-]+)\][+-]?[ \t]*(.*)$/;
 
-export type { Header, ChunkResult };
-
-export class Chunker {
+/**
+ * Heading-based chunking: one section per heading, long sections split by line
+ */
+export class Chunker implements ChunkStrategy {
     private maxChunkSize: number;
+    private includeHeadingInContent: boolean;
+    private splitOnHr: boolean;
+    private emptySections: EmptySectionBehavior;
+    private oversizedPolicy: OversizedPolicy;
 
-    constructor(maxChunkSize: number = 800) {
+    constructor(maxChunkSize: number = 800, options: ChunkerOptions = {}) {
         this.maxChunkSize = maxChunkSize;
+        this.includeHeadingInContent = options.includeHeadingInContent ?? true;
+        this.splitOnHr = options.splitOnHr ?? false;
+        this.emptySections = options.emptySections ?? 'keep';
+        this.oversizedPolicy = options.oversizedPolicy ?? 'split';
+    }
+
+    /**
+     * Currently active chunking options
+     */
+    getOptions(): Required<ChunkerOptions> {
+        return {
+            maxChunkSize: this.maxChunkSize,
+            includeHeadingInContent: this.includeHeadingInContent,
+            splitOnHr: this.splitOnHr,
+            emptySections: this.emptySections,
+            oversizedPolicy: this.oversizedPolicy,
+        };
+    }
+
+    /**
+     * Update chunking options, applies to the next chunk() call
+     */
+    updateOptions(options: ChunkerOptions): void {
+        if (options.maxChunkSize !== undefined) {
+            if (!Number.isInteger(options.maxChunkSize) || options.maxChunkSize < 1) {
+                throw new Error(`maxChunkSize must be a positive integer, got ${options.maxChunkSize}`);
+            }
+            this.maxChunkSize = options.maxChunkSize;
+        }
+        if (options.includeHeadingInContent !== undefined) {
+            this.includeHeadingInContent = options.includeHeadingInContent;
+        }
+        if (options.splitOnHr !== undefined) {
+            this.splitOnHr = options.splitOnHr;
+        }
+        if (options.emptySections !== undefined) {
+            this.emptySections = options.emptySections;
+        }
+        if (options.oversizedPolicy !== undefined) {
+            this.oversizedPolicy = options.oversizedPolicy;
+        }
     }
 
     /**
      * Chunk Markdown text into semantic blocks
+     * Line endings are normalized first; startPos/endPos index into the normalized content
      */
     chunk(content: string): ChunkResult[] {
         if (!content || content.trim().length === 0) {
             return [];
         }
 
+        content = normalizeNewlines(content);
+
         const headers = this.extractHeaders(content);
+        const breaks = this.splitOnHr ? this.findHorizontalRules(content) : [];
 
         if (headers.length === 0) {
             // No headers, split by length
-            return this.chunkWithoutHeaders(content);
+            return this.chunkWithoutHeaders(content, breaks);
         }
 
-        return this.splitByHeaders(content, headers);
+        return this.splitByHeaders(content, headers, breaks);
+    }
+
+    /**
+     * Chunk plain text by length only, `#` lines are not treated as headers
+     */
+    chunkPlainText(content: string): ChunkResult[] {
+        if (!content || content.trim().length === 0) {
+            return [];
+        }
+
+        return this.chunkWithoutHeaders(normalizeNewlines(content));
     }
 
     /**
@@ -47,8 +138,8 @@ export class Chunker {
                 // Count header level
                 const level = trimmed.match(/^#+/)?.[0].length || 0;
 
-                // Extract header text
-                const text = trimmed.slice(level).trim();
+                // Extract header text, without a trailing ^block-id or closing #s
+                const text = cleanHeadingText(trimmed.slice(level));
 
                 if (text.length > 0) {
                     headers.push({ level, text, position });
@@ -63,9 +154,15 @@ export class Chunker {
 
     /**
      * Split content by headers
+     * Without includeHeadingInContent each chunk starts after its heading line (startPos
+     * points at the body), the heading only survives in header_path, and heading-only
+     * sections produce no chunk
+     * With it, heading-only sections follow `emptySections`: "merge" starts the next
+     * non-empty section at the first pending heading, keeping that section's header_path
      */
-    private splitByHeaders(content: string, headers: Header[]): ChunkResult[] {
+    private splitByHeaders(content: string, headers: Header[], breaks: number[]): ChunkResult[] {
         const chunks: ChunkResult[] = [];
+        let mergedStart: number | undefined;
 
         for (let i = 0; i < headers.length; i++) {
             const header = headers[i];
@@ -75,61 +172,127 @@ export class Chunker {
                 ? headers[i + 1].position
                 : content.length;
 
-            // Extract chunk content
-            const chunkContent = content.slice(header.position, endPos);
+            const bodyStart = this.lineEnd(content, header.position, endPos);
+            const empty = content.slice(bodyStart, endPos).trim().length === 0;
+            if (empty && this.includeHeadingInContent && this.emptySections !== 'keep') {
+                if (this.emptySections === 'merge' && mergedStart === undefined) {
+                    mergedStart = header.position;
+                }
+                continue;
+            }
+
+            const startPos = this.includeHeadingInContent
+                ? (mergedStart ?? header.position)
+                : bodyStart;
+            mergedStart = undefined;
+            if (!this.includeHeadingInContent && empty) {
+                continue;
+            }
 
             // Build header path
             const headerPath = this.buildHeaderPath(headers, i);
-            const headerPathStr = this.formatHeaderPath(headerPath);
-
-            // Calculate line numbers
-            const startLine = this.calculateLineNumber(content, header.position);
-            const endLine = this.calculateLineNumber(content, endPos);
-
-            // If content is too long, split further
-            if (chunkContent.length > this.maxChunkSize) {
-                const parts = this.recursiveSplit(chunkContent, this.maxChunkSize);
-                let pos = header.position;
-
-                for (let j = 0; j < parts.length; j++) {
-                    const part = parts[j];
-                    const end = pos + part.length;
-
-                    const partStartLine = this.calculateLineNumber(content, pos);
-                    const partEndLine = this.calculateLineNumber(content, end);
-
-                    chunks.push({
-                        content: part,
-                        headers: headerPath,
-                        index: chunks.length,
-                        startPos: pos,
-                        endPos: end,
-                        start_line: partStartLine,
-                        end_line: partEndLine,
-                        header_path: headerPathStr,
-                    });
-
-                    pos = end;
-                }
-            } else {
+            this.pushSection(chunks, content, startPos, endPos, headerPath, breaks);
+        }
+
+        return chunks;
+    }
+
+    /**
+     * Emit chunks for the section content[start, end), sharing one header path
+     * Horizontal rule breaks inside the section start a new chunk (whitespace-only pieces
+     * are dropped), and pieces longer than maxChunkSize are split further
+     */
+    private pushSection(
+        chunks: ChunkResult[],
+        content: string,
+        start: number,
+        end: number,
+        headerPath: Array<{ level: number; text: string }>,
+        breaks: number[]
+    ): void {
+        const headerPathStr = this.formatHeaderPath(headerPath);
+        const bounds = breaks.filter(b => b > start && b < end).concat(end);
+        let segmentStart = start;
+
+        for (const bound of bounds) {
+            const segment = content.slice(segmentStart, bound);
+            if (bounds.length > 1 && segment.trim().length === 0) {
+                segmentStart = bound;
+                continue;
+            }
+
+            let pos = segmentStart;
+            for (const part of this.recursiveSplit(segment, this.maxChunkSize)) {
+                const partEnd = pos + part.length;
+
                 chunks.push({
-                    content: chunkContent,
+                    content: part,
                     headers: headerPath,
                     index: chunks.length,
-                    startPos: header.position,
-                    endPos: endPos,
-                    start_line: startLine,
-                    end_line: endLine,
+                    startPos: pos,
+                    endPos: partEnd,
+                    start_line: this.calculateLineNumber(content, pos),
+                    end_line: this.calculateLineNumber(content, partEnd),
                     header_path: headerPathStr,
+                    // Only an unbreakable line kept whole by recursiveSplit is longer than the limit
+                    ...(part.replace(/\n$/, '').length > this.maxChunkSize && { oversized: true }),
                 });
+
+                pos = partEnd;
             }
+            segmentStart = bound;
         }
+    }
 
-        return chunks;
+    /**
+     * Positions just after each thematic break line (`---`, `***`, `___`)
+     * Frontmatter fences, setext underlines (a `---` right below text) and
+     * lines inside code fences are not breaks
+     */
+    private findHorizontalRules(content: string): number[] {
+        const breaks: number[] = [];
+        const lines = content.split('\n');
+        let position = 0;
+        let inFence = false;
+        let inFrontmatter = lines[0] === '---';
+        let previous = '';
+
+        for (let i = 0; i < lines.length; i++) {
+            const line = lines[i];
+            const lineEnd = Math.min(position + line.length + 1, content.length);
+
+            if (inFrontmatter) {
+                if (i > 0 && (line === '---' || line === '...')) {
+                    inFrontmatter = false;
+                }
+            } else if (/^\s*(```|~~~)/.test(line)) {
+                inFence = !inFence;
+            } else if (!inFence && HORIZONTAL_RULE.test(line)) {
+                const setext = line.trim().startsWith('-') && previous.trim().length > 0;
+                if (!setext) {
+                    breaks.push(lineEnd);
+                }
+            }
+
+            previous = line;
+            position += line.length + 1;
+        }
+
+        return breaks;
+    }
+
+    /**
+     * Position just past the line starting at `position`, capped at `limit`
+     */
+    private lineEnd(content: string, position: number, limit: number): number {
+        const newline = content.indexOf('\n', position);
+        return newline === -1 ? limit : Math.min(newline + 1, limit);
     }
 
     /**
      * Build header hierarchy path
+     * Skipped levels are not filled in: an H3 after an H1 is a direct child of that H1,
+     * and a shallower heading pops every deeper or equal heading off the stack
      */
     private buildHeaderPath(headers: Header[], currentIndex: number): Array<{ level: number; text: string }> {
         const path: Array<{ level: number; text: string }> = [];
@@ -181,103 +344,106 @@ export class Chunker {
     /**
      * Chunk content without headers
      */
-    private chunkWithoutHeaders(content: string): ChunkResult[] {
+    private chunkWithoutHeaders(content: string, breaks: number[] = []): ChunkResult[] {
         const chunks: ChunkResult[] = [];
-
-        if (content.length <= this.maxChunkSize) {
-            const startLine = 1;
-            const endLine = content.split('\n').length;
-
-            chunks.push({
-                content,
-                headers: [],
-                index: 0,
-                startPos: 0,
-                endPos: content.length,
-                start_line: startLine,
-                end_line: endLine,
-                header_path: '',
-            });
-        } else {
-            // Split long content
-            const parts = this.recursiveSplit(content, this.maxChunkSize);
-            let pos = 0;
-
-            for (const part of parts) {
-                const end = pos + part.length;
-                const startLine = this.calculateLineNumber(content, pos);
-                const endLine = this.calculateLineNumber(content, end);
-
-                chunks.push({
-                    content: part,
-                    headers: [],
-                    index: chunks.length,
-                    startPos: pos,
-                    endPos: end,
-                    start_line: startLine,
-                    end_line: endLine,
-                    header_path: '',
-                });
-                pos = end;
-            }
-        }
-
+        this.pushSection(chunks, content, 0, content.length, [], breaks);
         return chunks;
     }
 
     /**
      * Recursive text splitting (fallback strategy)
+     * Lines (with their newline) and slices of over-long lines feed one accumulation
+     * loop, so parts stay close to maxLen and `parts.join('')` reproduces the content exactly
+     * A callout block is kept whole in one part; only a callout longer than maxLen is split by line
      */
     private recursiveSplit(content: string, maxLen: number): string[] {
-        const parts: string[] = [];
-
         if (content.length <= maxLen) {
-            parts.push(content);
-            return parts;
+            return [content];
         }
 
-        // Split by lines first
-        const lines = content.split('\n');
+        const parts: string[] = [];
         let currentChunk = '';
 
-        for (const line of lines) {
-            const lineWithNewline = line + '\n';
-
-            if (currentChunk.length + lineWithNewline.length > maxLen) {
-                if (currentChunk.length > 0) {
-                    parts.push(currentChunk.trimEnd());
-                    currentChunk = '';
+        for (const unit of this.splitUnits(content)) {
+            const lines = unit.length > maxLen ? splitLines(unit) : [unit];
+
+            for (const line of lines) {
+                // Measured without its newline: a line of exactly maxLen characters is not oversized
+                const oversized = line.replace(/\n$/, '').length > maxLen;
+                if (oversized && this.oversizedPolicy !== 'split' && UNBREAKABLE_LINE.test(line)) {
+                    if (currentChunk.length > 0) {
+                        parts.push(currentChunk);
+                        currentChunk = '';
+                    }
+                    parts.push(line);
+                    continue;
                 }
 
-                // If single line exceeds maxLen, force split
-                if (line.length > maxLen) {
-                    const lineParts = this.splitLongLine(line, maxLen);
-                    parts.push(...lineParts);
-                } else {
-                    currentChunk = lineWithNewline;
+                const pieces = line.length > maxLen ? this.splitLongLine(line, maxLen) : [line];
+
+                for (const piece of pieces) {
+                    if (currentChunk.length + piece.length > maxLen && currentChunk.length > 0) {
+                        parts.push(currentChunk);
+                        currentChunk = '';
+                    }
+                    currentChunk += piece;
                 }
-            } else {
-                currentChunk += lineWithNewline;
             }
         }
 
         if (currentChunk.length > 0) {
-            parts.push(currentChunk.trimEnd());
+            parts.push(currentChunk);
         }
 
         return parts;
     }
 
     /**
-     * Split a single long line
+     * Lines of the content, with each callout (its `> [!type]` line plus the
+     * following `>` lines) merged into a single unit
+     */
+    private splitUnits(content: string): string[] {
+        const units: string[] = [];
+        let callout = '';
+
+        for (const line of splitLines(content)) {
+            if (callout && /^\s*>/.test(line)) {
+                callout += line;
+                continue;
+            }
+            if (callout) {
+                units.push(callout);
+                callout = '';
+            }
+            if (CALLOUT_START.test(line.replace(/\n$/, ''))) {
+                callout = line;
+            } else {
+                units.push(line);
+            }
+        }
+
+        if (callout) {
+            units.push(callout);
+        }
+
+        return units;
+    }
+
+    /**
+     * Split a single long line into maxLen slices, never between a surrogate pair
      */
     private splitLongLine(line: string, maxLen: number): string[] {
         const parts: string[] = [];
         let remaining = line;
 
         while (remaining.length > maxLen) {
-            parts.push(remaining.slice(0, maxLen));
-            remaining = remaining.slice(maxLen);
+            let cut = maxLen;
+            const code = remaining.charCodeAt(cut);
+            if (code >= 0xdc00 && code <= 0xdfff && cut > 1) {
+                cut--;
+            }
+            parts.push(remaining.slice(0, cut));
+            remaining = remaining.slice(cut);
         }
 
         if (remaining.length > 0) {
@@ -287,3 +453,187 @@ export class Chunker {
         return parts;
     }
 }
+
+/**
+ * Heading text without an Obsidian `^block-id` suffix or closing `#` sequence
+ * e.g. " Section ## " => "Section", " Title ^abc-123" => "Title"
+ */
+function cleanHeadingText(text: string): string {
+    return text
+        .replace(/(^|\s)\^[A-Za-z0-9-]+\s*$/, '')
+        .replace(/(^|\s)#+\s*$/, '')
+        .trim();
+}
+
+/**
+ * Line-based chunking by length only, `#` lines are not treated as headers (for logs and plain text)
+ */
+export class FixedSizeChunker implements ChunkStrategy {
+    constructor(private chunker: Chunker) {}
+
+    chunk(content: string): ChunkResult[] {
+        return this.chunker.chunkPlainText(content);
+    }
+}
+
+/**
+ * Paragraph-based chunking for prose: blank-line separated paragraphs are packed
+ * whole up to maxChunkSize; only a paragraph longer than that is split by line
+ */
+export class ParagraphChunker implements ChunkStrategy {
+    constructor(private chunker: Chunker) {}
+
+    chunk(content: string): ChunkResult[] {
+        if (!content || content.trim().length === 0) {
+            return [];
+        }
+
+        content = normalizeNewlines(content);
+        const maxChunkSize = this.chunker.getOptions().maxChunkSize;
+        const chunks: ChunkResult[] = [];
+        let start = 0;
+        let end = 0;
+
+        const emit = (from: number, to: number, oversized?: boolean) => {
+            if (to > from) {
+                chunks.push({
+                    ...toPlainChunk(content, from, to, chunks.length),
+                    ...(oversized && { oversized }),
+                });
+            }
+        };
+
+        for (const paragraph of splitParagraphs(content)) {
+            if (end - start + paragraph.length > maxChunkSize) {
+                emit(start, end);
+                start = end;
+            }
+            if (paragraph.length > maxChunkSize) {
+                for (const part of this.chunker.chunkPlainText(paragraph)) {
+                    emit(end + part.startPos, end + part.endPos, part.oversized);
+                }
+                start = end + paragraph.length;
+            }
+            end += paragraph.length;
+        }
+        emit(start, end);
+
+        return chunks;
+    }
+}
+
+/**
+ * Strategy instance for a name; "header" and "fixed" share the given chunker's options
+ */
+export function createChunkStrategy(name: ChunkStrategyName, chunker: Chunker): ChunkStrategy {
+    switch (name) {
+        case 'paragraph':
+            return new ParagraphChunker(chunker);
+        case 'fixed':
+            return new FixedSizeChunker(chunker);
+        default:
+            return chunker;
+    }
+}
+
+/**
+ * Paragraphs of the content, each keeping the blank lines that follow it
+ */
+function splitParagraphs(content: string): string[] {
+    const paragraphs: string[] = [];
+    let current = '';
+    let blank = false;
+
+    for (const line of splitLines(content)) {
+        const isBlank = line.trim().length === 0;
+        if (!isBlank && blank && current) {
+            paragraphs.push(current);
+            current = '';
+        }
+        current += line;
+        blank = isBlank;
+    }
+
+    if (current) {
+        paragraphs.push(current);
+    }
+
+    return paragraphs;
+}
+
+/**
+ * Header-less chunk for content[start, end)
+ */
+function toPlainChunk(content: string, start: number, end: number, index: number): ChunkResult {
+    return {
+        content: content.slice(start, end),
+        headers: [],
+        index,
+        startPos: start,
+        endPos: end,
+        start_line: content.slice(0, start).split('\n').length,
+        end_line: content.slice(0, end).split('\n').length,
+        header_path: '',
+    };
+}
+
+/**
+ * Lines of the content, each keeping its trailing newline
+ */
+function splitLines(content: string): string[] {
+    return content.match(/[^\n]*\n|[^\n]+$/g) || [];
+}
+
+/**
+ * Type and title of each callout in the content, e.g. "> [!tip] Cache keys" => "tip: Cache keys"
+ */
+export function getCalloutLabels(content: string): string[] {
+    const labels: string[] = [];
+    for (const line of content.split('\n')) {
+        const match = line.match(CALLOUT_START);
+        if (match) {
+            const title = match[2].trim();
+            labels.push(title ? `${match[1]}: ${title}` : match[1]);
+        }
+    }
+    return labels;
+}
+
+// Number of equal-width histogram buckets between 0 and maxChunkSize
+const HISTOGRAM_BUCKETS = 5;
+
+/**
+ * Report how a document would be chunked, without embedding or indexing anything
+ * Pass the live chunker's options (`chunker.getOptions()`) and strategy so the report
+ * matches what indexing produces; `plainText` mirrors plain-text files
+ */
+export function analyzeChunks(
+    content: string,
+    options: ChunkerOptions & { strategy?: ChunkStrategyName; plainText?: boolean } = {}
+): ChunkReport {
+    const { strategy, plainText, ...chunkerOptions } = options;
+    const maxChunkSize = chunkerOptions.maxChunkSize ?? 800;
+    const chunker = new Chunker(maxChunkSize, chunkerOptions);
+    const chunks = plainText
+        ? chunker.chunkPlainText(content)
+        : createChunkStrategy(strategy ?? 'header', chunker).chunk(content);
+    const lengths = chunks.map(c => c.content.length);
+
+    const bucketWidth = Math.ceil(maxChunkSize / HISTOGRAM_BUCKETS);
+    const histogram = Array.from({ length: HISTOGRAM_BUCKETS }, (_, i) => ({
+        upTo: Math.min((i + 1) * bucketWidth, maxChunkSize),
+        count: 0,
+    }));
+    for (const length of lengths) {
+        const bucket = Math.min(Math.max(Math.ceil(length / bucketWidth) - 1, 0), HISTOGRAM_BUCKETS - 1);
+        histogram[bucket].count++;
+    }
+
+    return {
+        count: lengths.length,
+        minLen: lengths.length > 0 ? Math.min(...lengths) : 0,
+        maxLen: lengths.length > 0 ? Math.max(...lengths) : 0,
+        avgLen: lengths.length > 0 ? lengths.reduce((a, b) => a + b, 0) / lengths.length : 0,
+        histogram,
+    };
+}
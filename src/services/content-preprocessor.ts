@@ -1,3 +1,8 @@
+import { normalizeNewlines } from "@utils/text-utils";
+
+// Raw HTML image, e.g. <img alt="cat" src="pics/cat.png">; attribute order and quote style vary
+const HTML_IMAGE = /<img\b[^>]*\bsrc\s*=\s*(["'])[^"']+\1[^>]*>/i;
+
 export interface PreprocessResult {
 	cleaned: string;
 }
@@ -8,7 +13,7 @@ export class ContentPreprocessor {
 			return { cleaned: "" };
 		}
 
-		const lines = content.split("\n");
+		const lines = normalizeNewlines(content).split("\n");
 		const output: string[] = [];
 		let inCodeBlock = false;
 		let codeLang = "";
@@ -89,6 +94,7 @@ export class ContentPreprocessor {
 		if (!trimmedLine) return false;
 		if (trimmedLine.includes("![[") && trimmedLine.includes("]]"))
 			return true;
+		if (HTML_IMAGE.test(trimmedLine)) return true;
 		return /!\[[^\]]*\]\([^\)]+\)/.test(trimmedLine);
 	}
 
@@ -4,16 +4,27 @@
  */
 
 import type { EmbeddingProvider, EmbeddingConfig, BatchEmbeddingResult } from '@core/types/embedding';
-import { MODEL_DIMENSIONS } from '@core/types/embedding';
+import {
+    MODEL_DIMENSIONS,
+    DEFAULT_EMBEDDING_CONCURRENCY,
+    DEFAULT_EMBEDDING_MAX_INPUT_CHARS,
+    DEFAULT_OLLAMA_KEEP_ALIVE,
+} from '@core/types/embedding';
 import { getErrorMessage } from '@utils/error';
+import { truncateAtBoundary } from '@utils/text-utils';
+import { Semaphore } from '@utils/semaphore';
 
 export type { EmbeddingProvider, EmbeddingConfig, BatchEmbeddingResult };
 
 export class EmbeddingService {
     private config: EmbeddingConfig;;
+    private semaphore: Semaphore;
+    // null = not probed yet; false = older Ollama without /api/embed
+    private ollamaEmbedSupported: boolean | null = null;
 
     constructor(config: EmbeddingConfig) {
         this.config = config;
+        this.semaphore = new Semaphore(config.maxConcurrency || DEFAULT_EMBEDDING_CONCURRENCY);
         // Auto-detect dimension if not specified
         if (!this.config.dimension && this.config.ollamaModel) {
             this.config.dimension = MODEL_DIMENSIONS[this.config.ollamaModel] || 768;
@@ -41,6 +52,58 @@ export class EmbeddingService {
         if (config.openaiModel && !config.dimension) {
             this.config.dimension = MODEL_DIMENSIONS[config.openaiModel] || this.config.dimension;
         }
+        if (config.maxConcurrency) {
+            this.semaphore = new Semaphore(config.maxConcurrency);
+        }
+        if (config.ollamaUrl) {
+            this.ollamaEmbedSupported = null;
+        }
+    }
+
+    /**
+     * Get the configured model name for the current provider
+     */
+    getModelName(): string {
+        switch (this.config.provider) {
+            case 'ollama':
+                return this.config.ollamaModel || '';
+            case 'openai':
+                return this.config.openaiModel || 'text-embedding-3-small';
+            default:
+                return '';
+        }
+    }
+
+    /**
+     * Check the provider is reachable and the model is available, throws with an actionable message
+     * Ollama is probed via /api/tags; OpenAI only needs an API key (no request is made)
+     */
+    async checkHealth(): Promise<void> {
+        if (this.config.provider === 'openai') {
+            if (!this.config.openaiApiKey) {
+                throw new Error('未配置 OpenAI API Key');
+            }
+            return;
+        }
+
+        let response: Response;
+        try {
+            response = await fetch(`${this.config.ollamaUrl}/api/tags`, {
+                headers: { ...this.config.ollamaHeaders },
+            });
+        } catch (error) {
+            throw new Error(`无法连接到 Ollama (${this.config.ollamaUrl})，请确认 Ollama 已启动: ${getErrorMessage(error)}`);
+        }
+        if (!response.ok) {
+            throw new Error(`Ollama 返回错误: ${response.status} ${response.statusText}`);
+        }
+
+        const model = this.config.ollamaModel || '';
+        const data = await response.json();
+        const installed: string[] = (data.models || []).map((m: { name: string }) => m.name);
+        if (model && !installed.some(name => name === model || name === `${model}:latest`)) {
+            throw new Error(`Ollama 未安装模型 ${model}，请运行: ollama pull ${model}`);
+        }
     }
 
     /**
@@ -52,82 +115,137 @@ export class EmbeddingService {
 
     /**
      * Generate embedding for a single text
+     * In-flight requests are bounded by `maxConcurrency`, shared by all callers
+     * Inputs over `maxInputChars` are truncated at a sentence or word boundary
      */
     async embed(text: string): Promise<number[]> {
-        switch (this.config.provider) {
-            case 'ollama':
-                return this.embedOllama(text);
-            case 'openai':
-                return this.embedOpenAI(text);
-            default:
-                throw new Error(`Unknown provider: ${this.config.provider}`);
+        text = this.limitInput(text);
+        return this.semaphore.run(() => {
+            switch (this.config.provider) {
+                case 'ollama':
+                    return this.embedOllama(text);
+                case 'openai':
+                    return this.embedOpenAI(text);
+                default:
+                    throw new Error(`Unknown provider: ${this.config.provider}`);
+            }
+        });
+    }
+
+    private limitInput(text: string): string {
+        const maxChars = this.config.maxInputChars ?? DEFAULT_EMBEDDING_MAX_INPUT_CHARS;
+        if (maxChars <= 0 || text.length <= maxChars) {
+            return text;
         }
+
+        const truncated = truncateAtBoundary(text, maxChars);
+        if (truncated.length < text.length) {
+            console.warn(`[EmbeddingService] Input of ${text.length} chars truncated to ${truncated.length} (maxInputChars ${maxChars})`);
+        }
+        return truncated;
     }
 
     /**
-     * Generate embeddings for multiple texts
+     * Generate embeddings for multiple texts, concurrently up to `maxConcurrency`
+     * Each vector is stored at its text's index, so result i always belongs to texts[i]
+     * regardless of the order in which requests complete
      */
     async embedBatch(
         texts: string[],
         options: { continueOnError?: boolean } = {}
     ): Promise<number[][] | BatchEmbeddingResult> {
-        const results: number[][] = [];
+        const vectors: Array<number[] | null> = new Array(texts.length).fill(null);
         const failed: Array<{ index: number; error: Error }> = [];
 
-        for (let i = 0; i < texts.length; i++) {
+        await Promise.all(texts.map(async (text, index) => {
             try {
-                const embedding = await this.embed(texts[i]);
-                results.push(embedding);
+                vectors[index] = await this.embed(text);
             } catch (error) {
-                if (options.continueOnError) {
-                    failed.push({ index: i, error: error as Error });
-                } else {
+                if (!options.continueOnError) {
                     throw error;
                 }
+                failed.push({ index, error: error as Error });
             }
-        }
+        }));
 
         if (options.continueOnError) {
-            return { successful: results, failed };
+            failed.sort((a, b) => a.index - b.index);
+            return {
+                vectors,
+                successful: vectors.filter((v): v is number[] => v !== null),
+                failed,
+            };
         }
 
-        return results;
+        return vectors as number[][];
     }
 
     /**
      * Ollama embedding
+     * Falls back to the legacy /api/embeddings endpoint when /api/embed is missing (404)
+     * A 404 carrying an Ollama error (e.g. model not found) is a config error and is thrown instead
      */
     private async embedOllama(text: string): Promise<number[]> {
-        const url = `${this.config.ollamaUrl}/api/embed`;
-
         try {
-            const response = await fetch(url, {
-                method: 'POST',
-                headers: { 'Content-Type': 'application/json' },
-                body: JSON.stringify({
-                    model: this.config.ollamaModel,
-                    input: text,
-                }),
-            });
+            if (this.ollamaEmbedSupported !== false) {
+                const response = await this.postOllama('/api/embed', { input: text });
+
+                const modelError = response.status === 404 ? readOllamaError(await response.text()) : null;
+                if (modelError) {
+                    throw new Error(`Ollama API error: ${modelError}`);
+                }
+                if (response.status === 404) {
+                    this.ollamaEmbedSupported = false;
+                    console.warn('[EmbeddingService] Ollama /api/embed not found, falling back to /api/embeddings. Consider upgrading Ollama.');
+                } else {
+                    if (!response.ok) {
+                        throw new Error(`Ollama API error: ${response.statusText}`);
+                    }
+                    this.ollamaEmbedSupported = true;
+                    return this.parseOllamaEmbedding(await response.json());
+                }
+            }
 
+            const response = await this.postOllama('/api/embeddings', { prompt: text });
             if (!response.ok) {
                 throw new Error(`Ollama API error: ${response.statusText}`);
             }
+            return this.parseOllamaEmbedding(await response.json());
+        } catch (error) {
+            throw new Error(`Failed to generate Ollama embedding: ${getErrorMessage(error)}`);
+        }
+    }
 
-            const data = await response.json();
+    private postOllama(endpoint: string, body: Record<string, unknown>): Promise<Response> {
+        return fetch(`${this.config.ollamaUrl}${endpoint}`, {
+            method: 'POST',
+            headers: { ...this.config.ollamaHeaders, 'Content-Type': 'application/json' },
+            body: JSON.stringify({
+                model: this.config.ollamaModel,
+                keep_alive: this.getKeepAlive(),
+                ...body,
+            }),
+        });
+    }
 
-            if (Array.isArray(data.embeddings) && data.embeddings.length > 0) {
-                return data.embeddings[0];
-            }
+    /**
+     * Ollama reads a bare number as seconds and other values as durations ("30m", "1h")
+     */
+    private getKeepAlive(): string | number {
+        const keepAlive = (this.config.ollamaKeepAlive ?? DEFAULT_OLLAMA_KEEP_ALIVE).trim();
+        return /^-?\d+$/.test(keepAlive) ? Number(keepAlive) : keepAlive;
+    }
 
-            if (Array.isArray(data.embedding)) {
-                return data.embedding;
-            }
+    private parseOllamaEmbedding(data: any): number[] {
+        if (Array.isArray(data.embeddings) && Array.isArray(data.embeddings[0])) {
+            return coerceEmbedding(data.embeddings[0]);
+        }
 
-            throw new Error('Ollama response missing embedding');
-        } catch (error) {
-            throw new Error(`Failed to generate Ollama embedding: ${getErrorMessage(error)}`);
+        if (Array.isArray(data.embedding)) {
+            return coerceEmbedding(data.embedding);
         }
+
+        throw new Error('Ollama response missing embedding');
     }
 
     /**
@@ -158,6 +276,48 @@ export class EmbeddingService {
         }
 
         const data = await response.json();
-        return data.data[0].embedding;
+        return coerceEmbedding(data.data[0].embedding);
+    }
+}
+
+/**
+ * Embedding values as numbers; some Ollama-compatible servers send integers or numeric strings
+ * Throws on anything that is neither a finite number nor a numeric string
+ */
+export function coerceEmbedding(values: unknown[]): number[] {
+    return values.map((value, i) => {
+        const number = typeof value === 'string' && value.trim() !== '' ? Number(value) : value;
+        if (typeof number !== 'number' || !Number.isFinite(number)) {
+            throw new Error(`Embedding value at index ${i} is not a number: ${JSON.stringify(value)}`);
+        }
+        return number;
+    });
+}
+
+/**
+ * Parse `Name: value` lines into request headers, skipping blank lines and lines without a name
+ */
+export function parseHeaderLines(text: string): Record<string, string> {
+    const headers: Record<string, string> = {};
+    for (const line of text.split('\n')) {
+        const colon = line.indexOf(':');
+        const name = colon > 0 ? line.slice(0, colon).trim() : '';
+        if (name) {
+            headers[name] = line.slice(colon + 1).trim();
+        }
+    }
+    return headers;
+}
+
+/**
+ * The `error` message of an Ollama JSON error body, null for anything else
+ * (a missing endpoint answers with a plain "404 page not found")
+ */
+function readOllamaError(body: string): string | null {
+    try {
+        const data = JSON.parse(body);
+        return typeof data?.error === 'string' && data.error ? data.error : null;
+    } catch {
+        return null;
     }
 }
@@ -0,0 +1,136 @@
+/**
+ * IndexJobQueue - Fire-and-forget indexing jobs with pollable progress
+ * Jobs run one at a time; each job indexes its files with the index manager's bounded worker pool
+ * On shutdown, files already being indexed finish and everything else is marked cancelled
+ */
+
+import type { VectorIndexManager } from './vector-index-manager';
+import type { IndexJob, IndexJobState, IndexShutdownSummary } from '@core/types/indexing';
+import { generateUUID } from '@utils/uuid';
+import { getErrorMessage } from '@utils/error';
+
+export type { IndexJob, IndexJobState, IndexShutdownSummary };
+
+interface PendingJob {
+    id: string;
+    filePaths: string[];
+    read: (filePath: string) => Promise<string>;
+}
+
+export class IndexJobQueue {
+    private jobs: Map<string, IndexJob> = new Map();
+    private pending: PendingJob[] = [];
+    private draining: Promise<void> | null = null;
+    private shuttingDown = false;
+
+    constructor(private indexManager: Pick<VectorIndexManager, 'indexPaths'>) {}
+
+    /**
+     * Queue files for indexing and return the job id right away
+     * @param read - Loads a file's content when a worker picks it up
+     */
+    enqueue(filePaths: string[], read: (filePath: string) => Promise<string>): string {
+        const id = generateUUID();
+        this.jobs.set(id, { id, state: 'queued', processed: 0, total: filePaths.length, errors: [] });
+        if (this.shuttingDown) {
+            this.jobs.get(id)!.state = 'cancelled';
+            return id;
+        }
+        this.pending.push({ id, filePaths, read });
+
+        if (!this.draining) {
+            this.draining = this.drain().then(() => {
+                this.draining = null;
+            });
+        }
+        return id;
+    }
+
+    /**
+     * Snapshot of a job, undefined for an unknown id
+     */
+    getJob(id: string): IndexJob | undefined {
+        const job = this.jobs.get(id);
+        return job && { ...job, errors: [...job.errors] };
+    }
+
+    /**
+     * Snapshots of all jobs, oldest first
+     */
+    listJobs(): IndexJob[] {
+        return Array.from(this.jobs.values()).map(job => ({ ...job, errors: [...job.errors] }));
+    }
+
+    /**
+     * Resolves once every queued job has finished
+     */
+    async whenIdle(): Promise<void> {
+        await this.draining;
+    }
+
+    /**
+     * Cancel queued jobs, let the running job finish the files it has started and
+     * cancel its remaining files, then return what was completed vs abandoned
+     */
+    async shutdown(): Promise<IndexShutdownSummary> {
+        this.shuttingDown = true;
+        for (const { id } of this.pending.splice(0)) {
+            this.jobs.get(id)!.state = 'cancelled';
+        }
+        await this.draining;
+
+        const summary: IndexShutdownSummary = { completed: 0, failed: 0, cancelled: 0, abandonedFiles: 0 };
+        this.jobs.forEach(job => {
+            if (job.state === 'completed') {
+                summary.completed++;
+            } else if (job.state === 'failed') {
+                summary.failed++;
+            } else if (job.state === 'cancelled') {
+                summary.cancelled++;
+                summary.abandonedFiles += job.total - job.processed;
+            }
+        });
+        return summary;
+    }
+
+    private async drain(): Promise<void> {
+        let next: PendingJob | undefined;
+        while ((next = this.pending.shift())) {
+            const { id, filePaths, read } = next;
+            const job = this.jobs.get(id)!;
+            job.state = 'running';
+
+            // Files not yet picked up when shutdown starts are skipped, not reported as failures
+            const cancelled = new Set<string>();
+            const guardedRead = (filePath: string): Promise<string> => {
+                if (this.shuttingDown) {
+                    cancelled.add(filePath);
+                    return Promise.reject(new Error('Index job cancelled by shutdown'));
+                }
+                return read(filePath);
+            };
+
+            try {
+                const report = await this.indexManager.indexPaths(filePaths, guardedRead, ({ done }) => {
+                    job.processed = done - cancelled.size;
+                });
+                job.errors = report.files
+                    .filter(file => !file.success && !cancelled.has(file.filePath))
+                    .map(file => ({ filePath: file.filePath, error: file.error ?? '' }));
+                job.state = cancelled.size > 0 ? 'cancelled' : 'completed';
+            } catch (error) {
+                console.error('[MemoEcho] Index job failed:', id, error);
+                job.errors.push({ filePath: '', error: getErrorMessage(error) });
+                job.state = 'failed';
+            }
+        }
+    }
+}
+
+/**
+ * One-line shutdown summary for the log
+ */
+export function formatShutdownSummary(summary: IndexShutdownSummary): string {
+    return `${summary.completed} completed, ${summary.failed} failed, ` +
+        `${summary.cancelled} cancelled (${summary.abandonedFiles} file(s) not indexed)`;
+}
@@ -0,0 +1,479 @@
+/**
+ * MemoryBackend - In-memory implementation of VectorBackend
+ * Brute-force cosine search, for tests and small vaults without Qdrant
+ * Nothing is persisted: the index lives until the plugin is unloaded
+ */
+
+import {
+    VectorBackend,
+    MultiVectorItem,
+    SearchResult,
+    SearchOptions,
+    SearchFilter,
+    PointType,
+    PathInfo,
+    FacetField,
+    FacetCount,
+    BackendHealth,
+    ImportedPoint,
+    IndexRunScope,
+    VECTOR_NAMES,
+    ImportReport,
+    ReembedReport,
+    rrfFusion,
+    sortFacets,
+    toExportedPoint,
+    parseExportedLine,
+    pointDimension,
+} from './vector-backend';
+import { CONTENT_MULTI_VECTOR } from '@core/constants';
+import { cosineSimilarity } from '@utils/vector-utils';
+import { generateUUID } from '@utils/uuid';
+import { getAncestorFolders, normalizeFolder, normalizeVaultPath, toPathKey } from '@utils/path-utils';
+import type { ConceptPayload } from '@core/types/concept-registry';
+
+// Chunk points have the three chunk vectors, concept points the two concept vectors
+type StoredPoint = ImportedPoint;
+
+// Map key of a point: a re-indexed chunk sits next to the previous run's until that run is deleted
+function storageKey(item: { id: string; metadata: Record<string, any> }): string {
+    return item.metadata.indexRunId ? `${item.id}@${item.metadata.indexRunId}` : item.id;
+}
+
+export class MemoryBackend implements VectorBackend {
+    private points: Map<string, StoredPoint> = new Map();
+    private dimension: number | null = null;
+    private caseInsensitivePaths = false;
+
+    constructor(private collectionName: string = 'memory') {}
+
+    async initialize(): Promise<void> {
+        // Nothing to connect to
+    }
+
+    /**
+     * Match file paths ignoring case via the `filePathKey` payload field, like QdrantBackend
+     */
+    setCaseInsensitivePaths(enabled: boolean): void {
+        this.caseInsensitivePaths = enabled;
+    }
+
+    /**
+     * Sub-vectors are kept whenever an item has them; there is no collection schema to set up
+     */
+    setMultiVector(_enabled: boolean): void {
+        // Nothing to configure
+    }
+
+    async upsertMultiVector(item: MultiVectorItem): Promise<void> {
+        if (this.dimension === null) {
+            this.dimension = item.vectors[VECTOR_NAMES.CONTENT].length;
+        }
+        this.points.set(storageKey(item), {
+            id: item.id,
+            vectors: item.vectors,
+            ...(item.subVectors && { subVectors: item.subVectors }),
+            metadata: { ...item.metadata },
+        });
+    }
+
+    async searchWithFusion(
+        queryVector: number[],
+        options: SearchOptions = {}
+    ): Promise<SearchResult[]> {
+        const limit = options.limit || 10;
+        // Imported concept points carry no chunk vectors and are never search candidates
+        const candidates = Array.from(this.points.values()).filter((item) =>
+            item.vectors[VECTOR_NAMES.CONTENT] && this.matchesFilter(item, options.filter)
+        );
+
+        // Rank each named vector separately, then fuse like Qdrant's RRF query
+        const vectorNames = [VECTOR_NAMES.CONTENT, VECTOR_NAMES.SUMMARY, VECTOR_NAMES.TITLE];
+        const resultSets = vectorNames.map((name) =>
+            candidates
+                .map((item) => ({
+                    id: item.id,
+                    score: cosineSimilarity(queryVector, item.vectors[name]),
+                    metadata: item.metadata,
+                }))
+                .sort((a, b) => b.score - a.score)
+                .slice(0, limit * 2)
+        );
+
+        // Max-sim over paragraph sub-vectors, like Qdrant's multivector prefetch
+        const withSubVectors = candidates.filter((item) => item.subVectors?.length);
+        if (withSubVectors.length > 0) {
+            resultSets.push(
+                withSubVectors
+                    .map((item) => ({
+                        id: item.id,
+                        score: Math.max(...(item.subVectors ?? []).map((vector) => cosineSimilarity(queryVector, vector))),
+                        metadata: item.metadata,
+                    }))
+                    .sort((a, b) => b.score - a.score)
+                    .slice(0, limit * 2)
+            );
+        }
+
+        return rrfFusion(resultSets, limit).map((result) =>
+            options.withVectors
+                ? { ...result, vector: candidates.find((item) => item.id === result.id)?.vectors[VECTOR_NAMES.CONTENT] }
+                : result
+        );
+    }
+
+    async delete(id: string): Promise<void> {
+        this.deleteWhere((item) => item.id === id);
+    }
+
+    async deleteByFilePath(filePath: string, scope: IndexRunScope = {}): Promise<void> {
+        this.deleteWhere((item) => this.matchesPath(item, filePath) && this.inRunScope(item, scope));
+    }
+
+    async getPathInfo(filePath: string): Promise<PathInfo | null> {
+        const items = this.findByFilePath(filePath);
+        if (items.length === 0) {
+            return null;
+        }
+
+        const latest = items.reduce((a, b) =>
+            (b.metadata.indexedAt ?? 0) > (a.metadata.indexedAt ?? 0) ? b : a
+        );
+        return {
+            chunks: items.length,
+            latestIndexedAt: latest.metadata.indexedAt ?? null,
+            contentHash: latest.metadata.contentHash ?? null,
+        };
+    }
+
+    async getByFilePath(filePath: string): Promise<SearchResult[]> {
+        return this.findByFilePath(filePath)
+            .map((item) => ({ id: item.id, score: 1, metadata: item.metadata }))
+            .sort((a, b) => (a.metadata.start_line ?? 0) - (b.metadata.start_line ?? 0));
+    }
+
+    async renameFilePath(oldPath: string, newPath: string): Promise<number> {
+        const items = this.findByFilePath(oldPath);
+        for (const item of items) {
+            item.metadata.filePath = normalizeVaultPath(newPath);
+            item.metadata.filePathKey = toPathKey(newPath);
+            item.metadata.folders = getAncestorFolders(newPath);
+        }
+        return items.length;
+    }
+
+    async deleteByType(type: PointType): Promise<number> {
+        return this.deleteWhere((item) => item.metadata.type === type);
+    }
+
+    async deleteByTag(tag: string): Promise<number> {
+        const normalized = tag.trim().replace(/^#/, '');
+        if (!normalized) {
+            return 0;
+        }
+        return this.deleteWhere((item) => (item.metadata.tags ?? []).includes(normalized));
+    }
+
+    async facetCounts(field: FacetField, topK: number): Promise<FacetCount[]> {
+        const counts = new Map<string, number>();
+        this.points.forEach((item) => {
+            for (const value of item.metadata[field] ?? []) {
+                counts.set(value, (counts.get(value) ?? 0) + 1);
+            }
+        });
+        return sortFacets(Array.from(counts, ([value, count]) => ({ value, count }))).slice(0, topK);
+    }
+
+    async count(): Promise<number> {
+        return this.points.size;
+    }
+
+    async getHealth(): Promise<BackendHealth> {
+        return {
+            connected: true,
+            collectionName: this.collectionName,
+            dimension: this.dimension,
+            pointsCount: this.points.size,
+        };
+    }
+
+    async exportPoints(
+        write: (line: string) => void | Promise<void>,
+        options: { includeVectors?: boolean } = {}
+    ): Promise<number> {
+        let written = 0;
+        for (const item of Array.from(this.points.values())) {
+            const vectors = item.subVectors
+                ? { ...item.vectors, [CONTENT_MULTI_VECTOR]: item.subVectors }
+                : item.vectors;
+            const point = toExportedPoint(
+                item.id,
+                item.metadata,
+                options.includeVectors ? vectors : undefined
+            );
+            await write(JSON.stringify(point));
+            written++;
+        }
+        return written;
+    }
+
+    async importPoints(lines: string[], options: { recreate?: boolean } = {}): Promise<ImportReport> {
+        if (options.recreate) {
+            await this.clear();
+        }
+
+        const report: ImportReport = { imported: 0, skipped: 0 };
+        for (const line of lines) {
+            if (!line.trim()) {
+                continue;
+            }
+            const item = parseExportedLine(line);
+            const dimension = item && pointDimension(item);
+            if (!item || (this.dimension !== null && dimension !== this.dimension)) {
+                report.skipped++;
+                continue;
+            }
+            this.dimension = dimension;
+            this.points.set(storageKey(item), {
+                id: item.id,
+                vectors: item.vectors,
+                ...(item.subVectors && { subVectors: item.subVectors }),
+                metadata: { ...item.metadata },
+            });
+            report.imported++;
+        }
+        return report;
+    }
+
+    async reembedPoints(
+        embed: (metadata: Record<string, any>) => Promise<Pick<MultiVectorItem, 'vectors' | 'subVectors'>>,
+        onProgress?: (progress: ReembedReport) => void
+    ): Promise<ReembedReport> {
+        const report: ReembedReport = { updated: 0, failed: 0 };
+        for (const item of Array.from(this.points.values())) {
+            if (item.metadata.type === 'concept') {
+                continue;
+            }
+            try {
+                const { vectors, subVectors } = await embed(item.metadata);
+                item.vectors = vectors;
+                if (subVectors) {
+                    item.subVectors = subVectors;
+                } else {
+                    delete item.subVectors;
+                }
+                report.updated++;
+            } catch (error) {
+                console.warn('[Memory] Re-embedding failed for', item.id, error);
+                report.failed++;
+            }
+            onProgress?.({ ...report });
+        }
+        return report;
+    }
+
+    async optimize(): Promise<string | null> {
+        // Brute-force search has no index to build
+        return null;
+    }
+
+    async clear(dimension?: number): Promise<void> {
+        this.points.clear();
+        this.dimension = dimension ?? null;
+    }
+
+    // ==================== Concept Registry Methods ====================
+
+    /**
+     * Create or update a concept point, keeping the point id of an existing one
+     */
+    async upsertConcept(
+        concept: string,
+        summary: string,
+        link: string,
+        conceptVector: number[],
+        summaryVector: number[]
+    ): Promise<void> {
+        const existing = await this.getConcept(concept);
+        const now = new Date().toISOString();
+        const payload: ConceptPayload = existing
+            ? { ...existing.payload, summary, link, lastUsedAt: now }
+            : { type: 'concept', concept, summary, link, noteCount: 1, firstSeenAt: now, lastUsedAt: now };
+        const id = existing?.id ?? generateUUID();
+        this.points.set(id, {
+            id,
+            vectors: { concept_vec: conceptVector, concept_summary_vec: summaryVector },
+            metadata: payload,
+        });
+    }
+
+    /**
+     * Concepts by cosine similarity of concept_vec alone
+     */
+    async searchSimilarConceptsStrict(
+        queryVector: number[],
+        options: { limit?: number; scoreThreshold?: number } = {}
+    ): Promise<Array<{ id: string; score: number; payload: ConceptPayload }>> {
+        const scoreThreshold = options.scoreThreshold ?? 0.90;
+        return this.rankConcepts('concept_vec', queryVector, options.limit || 10)
+            .filter((result) => result.score >= scoreThreshold)
+            .map(({ id, score, metadata }) => ({ id, score, payload: metadata as ConceptPayload }));
+    }
+
+    /**
+     * Concepts by RRF fusion of concept_vec and concept_summary_vec, like Qdrant's fusion query
+     */
+    async searchSimilarConceptsLoose(
+        conceptVector: number[],
+        summaryVector: number[],
+        options: { limit?: number; scoreThreshold?: number } = {}
+    ): Promise<Array<{ id: string; score: number; payload: ConceptPayload }>> {
+        const limit = options.limit || 10;
+        const scoreThreshold = options.scoreThreshold ?? 0.85;
+        return rrfFusion([
+            this.rankConcepts('concept_vec', conceptVector, limit * 2),
+            this.rankConcepts('concept_summary_vec', summaryVector, limit * 2),
+        ], limit)
+            .filter((result) => result.score >= scoreThreshold)
+            .map(({ id, score, metadata }) => ({ id, score, payload: metadata as ConceptPayload }));
+    }
+
+    /**
+     * Page through concepts in insertion order; `offset` is the id of the first concept of the page
+     */
+    async scrollConcepts(options: { limit?: number; offset?: string } = {}
+    ): Promise<{ points: Array<{ payload: ConceptPayload }>; nextPage: string | null }> {
+        const limit = options.limit || 100;
+        const concepts = this.concepts();
+        const start = options.offset ? Math.max(0, concepts.findIndex((item) => item.id === options.offset)) : 0;
+        return {
+            points: concepts.slice(start, start + limit).map((item) => ({ payload: item.metadata as ConceptPayload })),
+            nextPage: concepts[start + limit]?.id ?? null,
+        };
+    }
+
+    async getConcept(concept: string): Promise<{ payload: ConceptPayload; id: string } | null> {
+        const item = this.concepts().find((point) => point.metadata.concept === concept);
+        return item ? { payload: item.metadata as ConceptPayload, id: item.id } : null;
+    }
+
+    /**
+     * Replace a concept's vectors and count one more use
+     */
+    async updateConceptUsageWithVectors(
+        concept: string,
+        conceptVector: number[],
+        summaryVector: number[]
+    ): Promise<void> {
+        const existing = this.points.get((await this.getConcept(concept))?.id ?? '');
+        if (!existing) {
+            console.warn(`[Memory] Concept not found for update: ${concept}`);
+            return;
+        }
+        existing.vectors = { concept_vec: conceptVector, concept_summary_vec: summaryVector };
+        existing.metadata = {
+            ...existing.metadata,
+            noteCount: (existing.metadata.noteCount ?? 0) + 1,
+            lastUsedAt: new Date().toISOString(),
+        };
+    }
+
+    private concepts(): StoredPoint[] {
+        return Array.from(this.points.values()).filter((item) => item.metadata.type === 'concept');
+    }
+
+    private rankConcepts(
+        vectorName: string,
+        queryVector: number[],
+        limit: number
+    ): Array<{ id: string; score: number; metadata: Record<string, any> }> {
+        return this.concepts()
+            .filter((item) => item.vectors[vectorName])
+            .map((item) => ({
+                id: item.id,
+                score: cosineSimilarity(queryVector, item.vectors[vectorName]),
+                metadata: item.metadata,
+            }))
+            .sort((a, b) => b.score - a.score)
+            .slice(0, limit);
+    }
+
+    private findByFilePath(filePath: string): StoredPoint[] {
+        return Array.from(this.points.values()).filter((item) => this.matchesPath(item, filePath));
+    }
+
+    /**
+     * Same file test as QdrantBackend's filePathCondition: `filePathKey` when ignoring case
+     */
+    private matchesPath(item: StoredPoint, filePath: string): boolean {
+        return this.caseInsensitivePaths
+            ? item.metadata.filePathKey === toPathKey(filePath)
+            : item.metadata.filePath === normalizeVaultPath(filePath);
+    }
+
+    /**
+     * Same run test as QdrantBackend's deleteByFilePath: untagged points are outside every run
+     */
+    private inRunScope(item: StoredPoint, scope: IndexRunScope): boolean {
+        const run = item.metadata.indexRunId;
+        return (!scope.indexRunId || run === scope.indexRunId)
+            && (!scope.exceptIndexRunId || run !== scope.exceptIndexRunId);
+    }
+
+    private deleteWhere(predicate: (item: StoredPoint) => boolean): number {
+        let deleted = 0;
+        for (const [id, item] of this.points) {
+            if (predicate(item)) {
+                this.points.delete(id);
+                deleted++;
+            }
+        }
+        return deleted;
+    }
+
+    private matchesFilter(item: StoredPoint, filter: SearchFilter | undefined): boolean {
+        if (!filter) {
+            return true;
+        }
+
+        if (filter.tags && filter.tags.length > 0) {
+            const tags: string[] = item.metadata.tags ?? [];
+            if (!filter.tags.some((tag) => tags.includes(tag))) {
+                return false;
+            }
+        }
+
+        if (filter.folder) {
+            const folders: string[] = item.metadata.folders ?? [];
+            if (!folders.includes(normalizeFolder(filter.folder))) {
+                return false;
+            }
+        }
+
+        if (filter.type && item.metadata.type !== filter.type) {
+            return false;
+        }
+
+        if (filter.excludeFilePath && this.matchesPath(item, filter.excludeFilePath)) {
+            return false;
+        }
+
+        const depth = item.metadata.depth;
+        if (filter.minDepth !== undefined && !(depth >= filter.minDepth)) {
+            return false;
+        }
+        if (filter.maxDepth !== undefined && !(depth <= filter.maxDepth)) {
+            return false;
+        }
+
+        const indexedAt = item.metadata.indexedAt;
+        if (filter.since !== undefined && !(indexedAt >= filter.since)) {
+            return false;
+        }
+        if (filter.until !== undefined && !(indexedAt <= filter.until)) {
+            return false;
+        }
+
+        return true;
+    }
+}
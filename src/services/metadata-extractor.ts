@@ -15,6 +15,8 @@ import {
 	METADATA_CONSTRAINTS,
 } from "@core/constants";
 import { BaseModelConfig } from "@core/types/setting";
+import { truncateChars } from "@utils/text-utils";
+import { parseModelJson } from "@utils/json-utils";
 
 export type { ExtractedMetadata, MetadataExtractorConfig };
 
@@ -77,12 +79,7 @@ export class MetadataExtractor {
 		}
 
 		const data = await response.json();
-		try {
-			const result = JSON.parse(data.response);
-			return this.normalizeResult(result);
-		} catch (error) {
-			throw new Error("Invalid JSON response from Ollama");
-		}
+		return this.normalizeResult(parseModelJson(data.response, "Ollama"));
 	}
 
 	private async extractWithOpenAI(
@@ -118,15 +115,10 @@ export class MetadataExtractor {
 		}
 
 		const data = await response.json();
-		try {
-			const contentStr = data.choices[0]?.message?.content || "{}";
-			// Handle markdown code blocks if present (OpenAI sometimes wraps JSON in ```json ... ```)
-			const cleanJson = contentStr.replace(/```json\n?|\n?```/g, "");
-			const result = JSON.parse(cleanJson);
-			return this.normalizeResult(result);
-		} catch (error) {
-			throw new Error("Invalid JSON response from OpenAI");
-		}
+		// OpenAI sometimes wraps JSON in ```json ... ```, parseModelJson strips it
+		return this.normalizeResult(
+			parseModelJson(data.choices[0]?.message?.content || "{}", "OpenAI"),
+		);
 	}
 
 	private normalizeResult(result: any): ExtractedMetadata {
@@ -150,11 +142,12 @@ export class MetadataExtractor {
 	 * Build prompt for Ollama/OpenAI
 	 */
 	private buildPrompt(content: string): string {
+		const truncated = truncateChars(
+			content,
+			METADATA_CONSTRAINTS.maxSummaryLength,
+		);
 		const truncatedContent =
-			content.length > METADATA_CONSTRAINTS.maxSummaryLength
-				? content.substring(0, METADATA_CONSTRAINTS.maxSummaryLength) +
-					"..."
-				: content;
+			truncated.length < content.length ? truncated + "..." : content;
 
 		return `请分析以下 Markdown 段落，提取关键信息。
 段落内容：
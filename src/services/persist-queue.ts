@@ -18,6 +18,7 @@ export class PersistQueue {
     private flushTimer: NodeJS.Timeout | null = null;
     private stats: QueueStats;
     private useMultiVector: boolean;
+    private multiVectorFlush: Promise<void> = Promise.resolve();
 
     constructor(
         backend: VectorBackend,
@@ -69,6 +70,13 @@ export class PersistQueue {
         }
     }
 
+    /**
+     * Change how many queued chunks trigger an automatic flush
+     */
+    setBatchSize(batchSize: number): void {
+        this.batchSize = batchSize;
+    }
+
     /**
      * Get queue size
      */
@@ -179,8 +187,15 @@ export class PersistQueue {
 
     /**
      * Flush multi-vector queue to vector store (v0.4.0)
+     * Concurrent calls run one after another, so no chunk is upserted twice
      */
     async flushMultiVector(): Promise<void> {
+        const flush = this.multiVectorFlush.then(() => this.flushMultiVectorNow());
+        this.multiVectorFlush = flush.catch(() => undefined);
+        return flush;
+    }
+
+    private async flushMultiVectorNow(): Promise<void> {
         if (this.multiVectorQueue.size === 0) {
             return;
         }
@@ -193,16 +208,18 @@ export class PersistQueue {
                 await this.backend.upsertMultiVector({
                     id: chunk.id,
                     vectors: chunk.vectors,
+                    ...(chunk.subVectors && { subVectors: chunk.subVectors }),
                     metadata: chunk.metadata,
                 });
+                // Keep a chunk re-enqueued during the flush, and leave unflushed ones on failure
+                if (this.multiVectorQueue.get(chunk.id) === chunk) {
+                    this.multiVectorQueue.delete(chunk.id);
+                }
             }
 
             // Update stats
             this.stats.totalFlushed += chunks.length;
             this.stats.flushCount++;
-
-            // Clear queue after successful flush
-            this.multiVectorQueue.clear();
         } catch (error) {
             this.stats.failedFlushes++;
             throw error;
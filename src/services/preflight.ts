@@ -0,0 +1,74 @@
+/**
+ * Preflight - Startup readiness checks for the embedding provider and vector store
+ * Failures leave the plugin loaded in a degraded mode with an actionable message
+ */
+
+import { getErrorMessage } from '@utils/error';
+
+export interface PreflightChecks {
+    checkEmbedding: () => Promise<void>; // Throws if the embedding provider is unusable
+    checkVectorStore: () => Promise<void>; // Throws if the vector store is unreachable
+}
+
+export interface PreflightOptions {
+    embeddingRetries?: number; // Extra attempts for the embedding check (Ollama may still be starting)
+    retryDelayMs?: number;
+}
+
+export interface PreflightResult {
+    ready: boolean;
+    embeddingReady: boolean;
+    vectorStoreReady: boolean;
+    problems: string[]; // One message per failed dependency
+}
+
+/**
+ * Run both checks in parallel; a healthy startup costs one round-trip each
+ */
+export async function runPreflight(
+    checks: PreflightChecks,
+    options: PreflightOptions = {}
+): Promise<PreflightResult> {
+    const { embeddingRetries = 2, retryDelayMs = 500 } = options;
+
+    const [embeddingError, vectorStoreError] = await Promise.all([
+        attempt(checks.checkEmbedding, embeddingRetries, retryDelayMs),
+        attempt(checks.checkVectorStore, 0, retryDelayMs),
+    ]);
+
+    const problems: string[] = [];
+    if (embeddingError) {
+        problems.push(`嵌入服务不可用: ${embeddingError}`);
+    }
+    if (vectorStoreError) {
+        problems.push(`向量数据库不可用: ${vectorStoreError}`);
+    }
+
+    return {
+        ready: problems.length === 0,
+        embeddingReady: !embeddingError,
+        vectorStoreReady: !vectorStoreError,
+        problems,
+    };
+}
+
+/**
+ * Returns the last error message, or null once the check passes
+ */
+async function attempt(
+    check: () => Promise<void>,
+    retries: number,
+    retryDelayMs: number
+): Promise<string | null> {
+    for (let i = 0; ; i++) {
+        try {
+            await check();
+            return null;
+        } catch (error) {
+            if (i >= retries) {
+                return getErrorMessage(error);
+            }
+            await new Promise((resolve) => setTimeout(resolve, retryDelayMs));
+        }
+    }
+}
@@ -4,20 +4,156 @@
  */
 
 import { QdrantClient } from '@qdrant/js-client-rest';
+import type { Schemas } from '@qdrant/js-client-rest';
 import {
     VectorBackend,
     MultiVectorItem,
     SearchResult,
     SearchOptions,
+    SearchFilter,
+    PointType,
+    PathInfo,
+    FacetField,
+    FacetCount,
+    BackendHealth,
+    ImportedPoint,
+    IndexRunScope,
+    ImportReport,
+    ReembedReport,
     VECTOR_NAMES,
+    compareSearchResults,
+    sortFacets,
+    toExportedPoint,
+    parseExportedLine,
+    pointDimension,
 } from './vector-backend';
-import { generateUUID } from '@utils/uuid';
+import { generateUUID, uuidFromKey } from '@utils/uuid';
+import { getErrorMessage } from '@utils/error';
+import { getAncestorFolders, normalizeFolder, normalizeVaultPath, toPathKey } from '@utils/path-utils';
 import { Notice } from 'obsidian';
 import type { ConceptPayload } from '@core/types/concept-registry';
+import type { QdrantTuningConfig } from '@core/types/setting';
+import { DEFAULT_QDRANT_TUNING_CONFIG } from '@core/types/setting';
+import { CONTENT_MULTI_VECTOR, DEFAULT_PAYLOAD_INDEXES } from '@core/constants';
+import type { PayloadIndexType } from '@core/constants';
+
+// Client timeout and startup retry behaviour
+export interface QdrantConnectionOptions {
+    timeoutMs?: number;
+    retries?: number; // Extra connection attempts in initialize(), with exponential backoff
+    retryDelayMs?: number;
+}
 
 // Minimal interface for embedding service dimension access
-interface EmbeddingServiceDimension {
+// getDimension is a lookup-table guess; embed gives the real dimension
+export interface EmbeddingServiceDimension {
     getDimension(): number;
+    embed(text: string): Promise<number[]>;
+}
+
+// Points per upsert request when importing an export
+const IMPORT_BATCH_SIZE = 64;
+
+// Points embedded and updated per page when re-embedding
+const REEMBED_PAGE_SIZE = 64;
+
+/**
+ * Length of a real embedding, null if the embedding service can't be reached
+ * (the first upsert still checks the dimension then)
+ */
+async function probeDimension(embeddingService: EmbeddingServiceDimension): Promise<number | null> {
+    try {
+        return (await embeddingService.embed('dimension probe')).length;
+    } catch (error) {
+        console.warn('[Qdrant] Dimension probe failed, checking on first upsert instead:', getErrorMessage(error));
+        return null;
+    }
+}
+
+/**
+ * Filter condition selecting all points of a file
+ */
+function filePathCondition(filePath: string, caseInsensitive: boolean): Schemas['Condition'] {
+    return caseInsensitive
+        ? { key: 'filePathKey', match: { value: toPathKey(filePath) } }
+        : { key: 'filePath', match: { value: normalizeVaultPath(filePath) } };
+}
+
+/**
+ * Compose search filter criteria into one Qdrant filter, undefined when nothing is filtered
+ * Tags match any of the given tags; every other criterion is ANDed
+ */
+export function buildQdrantFilter(
+    filter: SearchFilter | undefined,
+    caseInsensitivePaths: boolean = false
+): Schemas['Filter'] | undefined {
+    if (!filter) {
+        return undefined;
+    }
+
+    const must: Schemas['Condition'][] = [];
+    const mustNot: Schemas['Condition'][] = [];
+    if (filter.tags && filter.tags.length > 0) {
+        must.push({ key: 'tags', match: { any: filter.tags } });
+    }
+    if (filter.folder) {
+        must.push({ key: 'folders', match: { value: normalizeFolder(filter.folder) } });
+    }
+    if (filter.type) {
+        must.push({ key: 'type', match: { value: filter.type } });
+    }
+    if (filter.since !== undefined || filter.until !== undefined) {
+        must.push({ key: 'indexedAt', range: { gte: filter.since, lte: filter.until } });
+    }
+    if (filter.minDepth !== undefined || filter.maxDepth !== undefined) {
+        must.push({ key: 'depth', range: { gte: filter.minDepth, lte: filter.maxDepth } });
+    }
+    if (filter.excludeFilePath) {
+        mustNot.push(filePathCondition(filter.excludeFilePath, caseInsensitivePaths));
+    }
+
+    if (must.length === 0 && mustNot.length === 0) {
+        return undefined;
+    }
+    return {
+        ...(must.length > 0 && { must }),
+        ...(mustNot.length > 0 && { must_not: mustNot }),
+    };
+}
+
+/**
+ * Thrown by connect() when the collection has not been created
+ */
+export class CollectionNotFoundError extends Error {
+    constructor(public readonly collectionName: string) {
+        super(`Qdrant 集合不存在: ${collectionName}`);
+        this.name = 'CollectionNotFoundError';
+    }
+}
+
+/**
+ * Whether a collection was created with the per-paragraph content multivector
+ */
+function hasMultiVector(collection: Schemas['CollectionInfo']): boolean {
+    const vectors = collection.config?.params?.vectors as Record<string, unknown> | undefined;
+    return Boolean(vectors?.[CONTENT_MULTI_VECTOR]);
+}
+
+/**
+ * Qdrant point id for an imported point: the exported point id, the id itself when it is
+ * already a valid point id (concepts), otherwise a UUID derived from it so re-imports overwrite
+ */
+function importedPointId(point: ImportedPoint): string | number {
+    if (point.pointId !== undefined) {
+        return point.pointId;
+    }
+    if (/^[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$/i.test(point.id)) {
+        return point.id;
+    }
+    if (/^\d+$/.test(point.id)) {
+        return Number(point.id);
+    }
+    return uuidFromKey(point.id);
 }
 
 export class QdrantBackend implements VectorBackend {
@@ -25,27 +161,148 @@ export class QdrantBackend implements VectorBackend {
     private collectionName: string;
     private qdrantUrl: string;
     private vectorSize: number | null = null;
+    private tuning?: QdrantTuningConfig;
+    private connection: Required<QdrantConnectionOptions>;
+    private caseInsensitivePaths = false;
+    private multiVector = false;
+    private collectionHasMultiVector = false;
+    private payloadIndexes: Record<string, PayloadIndexType> = { ...DEFAULT_PAYLOAD_INDEXES };
 
     constructor(
         collectionName: string = 'obsidian_notes',
         qdrantUrl: string = 'http://localhost:6333',
-        private embeddingService?: EmbeddingServiceDimension
+        private embeddingService?: EmbeddingServiceDimension,
+        tuning?: QdrantTuningConfig,
+        connection: QdrantConnectionOptions = {}
     ) {
-        this.client = new QdrantClient({ url: qdrantUrl });
+        this.connection = {
+            timeoutMs: connection.timeoutMs ?? 10000,
+            retries: connection.retries ?? 3,
+            retryDelayMs: connection.retryDelayMs ?? 500,
+        };
+        this.client = new QdrantClient({ url: qdrantUrl, timeout: this.connection.timeoutMs });
         this.collectionName = collectionName;
         this.qdrantUrl = qdrantUrl;
+        this.tuning = tuning;
+    }
+
+    /**
+     * Create a handle for another collection that reuses an existing client
+     */
+    static withClient(
+        client: QdrantClient,
+        collectionName: string,
+        qdrantUrl: string = 'http://localhost:6333',
+        embeddingService?: EmbeddingServiceDimension,
+        tuning?: QdrantTuningConfig
+    ): QdrantBackend {
+        const backend = new QdrantBackend(collectionName, qdrantUrl, embeddingService, tuning);
+        backend.client = client;
+        return backend;
+    }
+
+    /**
+     * Underlying client, for sharing with other collection handles
+     */
+    getClient(): QdrantClient {
+        return this.client;
+    }
+
+    /**
+     * Update collection tuning, used the next time the collection is created
+     */
+    setCollectionTuning(tuning: Partial<QdrantTuningConfig>): void {
+        this.tuning = { ...this.tuning, ...tuning } as QdrantTuningConfig;
+    }
+
+    /**
+     * Match file paths ignoring case via the `filePathKey` payload field
+     * Points indexed before this field existed only match in case-sensitive mode
+     */
+    setCaseInsensitivePaths(enabled: boolean): void {
+        this.caseInsensitivePaths = enabled;
+    }
+
+    /**
+     * Store and search per-paragraph sub-vectors in a max-sim multivector
+     * Only collections created while enabled have it; existing ones need clearing first
+     */
+    setMultiVector(enabled: boolean): void {
+        this.multiVector = enabled;
     }
 
+    /**
+     * Filter condition selecting all points of a file
+     */
+    private filePathCondition(filePath: string): Schemas['Condition'] {
+        return filePathCondition(filePath, this.caseInsensitivePaths);
+    }
+
+    /**
+     * Set which payload fields get an index when the collection is initialized
+     */
+    setPayloadIndexes(fields: Record<string, PayloadIndexType>): void {
+        this.payloadIndexes = { ...fields };
+    }
+
+    /**
+     * Wait until Qdrant is reachable, retrying with exponential backoff
+     * The collection itself is still created on first upsert
+     */
     async initialize(): Promise<void> {
-        // No-op, collection created on first upsert
+        const { retries, retryDelayMs } = this.connection;
+
+        for (let attempt = 0; ; attempt++) {
+            let collections: Array<{ name: string }>;
+            try {
+                ({ collections } = await this.client.getCollections());
+            } catch (error) {
+                if (attempt >= retries) {
+                    throw new Error(`无法连接到 Qdrant 服务 (${this.qdrantUrl}): ${getErrorMessage(error)}`);
+                }
+                const delay = retryDelayMs * 2 ** attempt;
+                console.warn(`[Qdrant] Connection attempt ${attempt + 1} failed, retrying in ${delay}ms`);
+                await new Promise((resolve) => setTimeout(resolve, delay));
+                continue;
+            }
+
+            // Catch an embedding model change at startup instead of on the first upsert
+            if (this.embeddingService && collections.some((c) => c.name === this.collectionName)) {
+                const dimension = await probeDimension(this.embeddingService);
+                if (dimension !== null) {
+                    const collection = await this.client.getCollection(this.collectionName);
+                    this.assertCompatibleVectors(collection, dimension);
+                }
+            }
+            return;
+        }
+    }
+
+    /**
+     * Like initialize, but require the collection to exist instead of creating it on first upsert
+     * Use when the collection is managed elsewhere; writes then go straight to the existing collection
+     */
+    async connect(): Promise<void> {
+        await this.initialize();
+
+        const { collections } = await this.client.getCollections();
+        if (!collections.some((c) => c.name === this.collectionName)) {
+            throw new CollectionNotFoundError(this.collectionName);
+        }
+
+        const collection = await this.client.getCollection(this.collectionName);
+        const vectors = collection.config?.params?.vectors as Record<string, { size?: number }> | undefined;
+        this.vectorSize = vectors?.[VECTOR_NAMES.CONTENT]?.size ?? null;
+        this.collectionHasMultiVector = hasMultiVector(collection);
     }
 
     async upsertMultiVector(item: MultiVectorItem): Promise<void> {
         // Auto-detect dimension from first vector
         if (this.vectorSize === null) {
-            this.vectorSize = item.vectors[VECTOR_NAMES.CONTENT].length;
-            console.log(`[Qdrant] Auto-detected vector dimension: ${this.vectorSize}`);
-            await this.ensureCollection(this.vectorSize);
+            const dimension = item.vectors[VECTOR_NAMES.CONTENT].length;
+            console.log(`[Qdrant] Auto-detected vector dimension: ${dimension}`);
+            await this.ensureCollection(dimension);
+            this.vectorSize = dimension;
         }
 
         const uuid = generateUUID();
@@ -58,6 +315,9 @@ export class QdrantBackend implements VectorBackend {
                         [VECTOR_NAMES.CONTENT]: item.vectors[VECTOR_NAMES.CONTENT],
                         [VECTOR_NAMES.SUMMARY]: item.vectors[VECTOR_NAMES.SUMMARY],
                         [VECTOR_NAMES.TITLE]: item.vectors[VECTOR_NAMES.TITLE],
+                        ...(this.collectionHasMultiVector && item.subVectors?.length && {
+                            [CONTENT_MULTI_VECTOR]: item.subVectors,
+                        }),
                     },
                     payload: {
                         ...item.metadata,
@@ -71,9 +331,13 @@ export class QdrantBackend implements VectorBackend {
     private async ensureCollection(dimension: number): Promise<void> {
         console.log(`[Qdrant] ensureCollection called with dimension: ${dimension}`);
 
+        let existingIndexes: Record<string, unknown> = {};
+        let existing: Schemas['CollectionInfo'] | null = null;
+
         try {
-            const collection = await this.client.getCollection(this.collectionName);
-            console.log('[Qdrant] Collection exists:', collection.config?.params?.vectors);
+            existing = await this.client.getCollection(this.collectionName);
+            console.log('[Qdrant] Collection exists:', existing.config?.params?.vectors);
+            existingIndexes = existing.payload_schema || {};
         } catch (error: any) {
             // Collection doesn't exist, try to create it
             console.log(`[Qdrant] Collection doesn't exist, creating with Named Vectors, dimension ${dimension}`);
@@ -87,8 +351,34 @@ export class QdrantBackend implements VectorBackend {
                         // Concept vectors (v0.7.0+)
                         concept_vec: { size: dimension, distance: 'Cosine' },
                         concept_summary_vec: { size: dimension, distance: 'Cosine' },
+                        ...(this.multiVector && {
+                            [CONTENT_MULTI_VECTOR]: {
+                                size: dimension,
+                                distance: 'Cosine',
+                                multivector_config: { comparator: 'max_sim' },
+                            },
+                        }),
                     },
+                    ...(this.tuning && {
+                        hnsw_config: {
+                            m: this.tuning.hnswM,
+                            ef_construct: this.tuning.hnswEfConstruct,
+                        },
+                        on_disk_payload: this.tuning.onDiskPayload,
+                        ...(this.tuning.scalarQuantization && {
+                            quantization_config: {
+                                scalar: {
+                                    type: 'int8',
+                                    quantile: this.tuning.quantizationQuantile
+                                        ?? DEFAULT_QDRANT_TUNING_CONFIG.quantizationQuantile,
+                                    always_ram: this.tuning.quantizationAlwaysRam
+                                        ?? DEFAULT_QDRANT_TUNING_CONFIG.quantizationAlwaysRam,
+                                },
+                            },
+                        }),
+                    }),
                 });
+                this.collectionHasMultiVector = this.multiVector;
                 console.log('[Qdrant] Collection created successfully');
             } catch (createError: any) {
                 console.error('[Qdrant] Failed to create collection:', createError);
@@ -104,9 +394,67 @@ export class QdrantBackend implements VectorBackend {
             }
         }
 
+        if (existing) {
+            this.assertCompatibleVectors(existing, dimension);
+            this.collectionHasMultiVector = hasMultiVector(existing);
+            if (this.multiVector && !this.collectionHasMultiVector) {
+                console.warn('[Qdrant] Collection has no multivector, clear the index to store sub-vectors');
+            }
+        }
+
+        await this.ensurePayloadIndexes(existingIndexes);
+
         console.log(`[Qdrant] ensureCollection completed for collection: ${this.collectionName}`);
     }
 
+    /**
+     * Throw if an existing collection's content vectors don't match the configured dimension
+     * Usually means the embedding model changed; clearing the index recreates the collection
+     */
+    private assertCompatibleVectors(collection: Schemas['CollectionInfo'], dimension: number): void {
+        const vectors = collection.config?.params?.vectors as Record<string, { size?: number; distance?: string }> | undefined;
+        const content = vectors?.[VECTOR_NAMES.CONTENT];
+        if (!content) {
+            return;
+        }
+
+        if (content.size !== dimension) {
+            throw new Error(
+                `集合 ${this.collectionName} 的向量维度为 ${content.size}，当前嵌入模型为 ${dimension} 维。` +
+                '请在设置中清空索引后重新索引，或切换回原来的模型'
+            );
+        }
+        if (content.distance && content.distance !== 'Cosine') {
+            throw new Error(
+                `集合 ${this.collectionName} 使用 ${content.distance} 距离，插件需要 Cosine。请在设置中清空索引后重新索引`
+            );
+        }
+    }
+
+    /**
+     * Create payload indexes for filtered fields, skipping ones that already exist
+     */
+    private async ensurePayloadIndexes(existingIndexes: Record<string, unknown>): Promise<void> {
+        for (const field of Object.keys(this.payloadIndexes)) {
+            const schema = this.payloadIndexes[field];
+            if (field in existingIndexes) {
+                continue;
+            }
+
+            try {
+                await this.client.createPayloadIndex(this.collectionName, {
+                    field_name: field,
+                    field_schema: schema,
+                    wait: true,
+                });
+                console.log(`[Qdrant] Created ${schema} payload index on ${field}`);
+            } catch (error) {
+                // Index is an optimization only, filtering still works without it
+                console.warn(`[Qdrant] Failed to create payload index on ${field}:`, error);
+            }
+        }
+    }
+
     async searchWithFusion(
         queryVector: number[],
         options: SearchOptions = {}
@@ -115,9 +463,12 @@ export class QdrantBackend implements VectorBackend {
         const prefetchLimit = limit * 2;
 
         // Check if collection exists, if not create it with query vector dimension
+        let useMultiVector = false;
         try {
             const collection = await this.client.getCollection(this.collectionName);
-            if (!collection.points_count || collection.points_count === 0) {
+            useMultiVector = this.multiVector && hasMultiVector(collection);
+            // A missing points_count (e.g. during optimization) is unknown, not empty
+            if ((await this.resolvePointsCount(collection.points_count)) === 0) {
                 console.log('[Qdrant] Collection is empty, returning empty results');
                 return [];
             }
@@ -129,18 +480,7 @@ export class QdrantBackend implements VectorBackend {
             return [];
         }
 
-        // Build filter condition if tags provided
-        let filterCondition: any = undefined;
-        if (options.filter?.tags && options.filter.tags.length > 0) {
-            filterCondition = {
-                must: [
-                    {
-                        key: 'tags',
-                        match: { any: options.filter.tags },
-                    },
-                ],
-            };
-        }
+        const filterCondition = buildQdrantFilter(options.filter, this.caseInsensitivePaths);
 
         // Use Qdrant Query API for native RRF fusion
         const results = await this.client.query(this.collectionName, {
@@ -163,22 +503,34 @@ export class QdrantBackend implements VectorBackend {
                     limit: prefetchLimit,
                     filter: filterCondition,
                 },
+                // Max-sim of the query against each paragraph, so one matching paragraph is enough
+                ...(useMultiVector ? [{
+                    query: [queryVector],
+                    using: CONTENT_MULTI_VECTOR,
+                    limit: prefetchLimit,
+                    filter: filterCondition,
+                }] : []),
             ],
             query: { fusion: 'rrf' },
             limit,
             with_payload: true,
+            with_vector: options.withVectors ? [VECTOR_NAMES.CONTENT] : false,
         });
 
-        return results.points.map(point => {
+        return results.points.map((point): SearchResult => {
             const payload = point.payload as any;
             const { _customId, ...metadata } = payload;
+            const vectors = point.vector as Record<string, number[]> | undefined;
 
             return {
                 id: _customId,
                 score: point.score || 0,
                 metadata,
+                ...(options.withVectors && vectors?.[VECTOR_NAMES.CONTENT] && {
+                    vector: vectors[VECTOR_NAMES.CONTENT],
+                }),
             };
-        });
+        }).sort(compareSearchResults);
     }
 
     async delete(id: string): Promise<void> {
@@ -194,35 +546,400 @@ export class QdrantBackend implements VectorBackend {
         });
     }
 
-    async deleteByFilePath(filePath: string): Promise<void> {
+    async deleteByFilePath(filePath: string, scope: IndexRunScope = {}): Promise<void> {
+        const run = (indexRunId: string): Schemas['Condition'] => ({ key: 'indexRunId', match: { value: indexRunId } });
         await this.client.delete(this.collectionName, {
             filter: {
                 must: [
-                    {
-                        key: 'filePath',
-                        match: { value: filePath },
-                    },
+                    this.filePathCondition(filePath),
+                    ...(scope.indexRunId ? [run(scope.indexRunId)] : []),
                 ],
+                // Points indexed before runs were tagged lack the field, so they count as outside the run
+                ...(scope.exceptIndexRunId && { must_not: [run(scope.exceptIndexRunId)] }),
+            },
+        });
+    }
+
+    async getPathInfo(filePath: string): Promise<PathInfo | null> {
+        const points = await this.scrollByFilePath(filePath, ['indexedAt', 'contentHash']);
+        if (points.length === 0) {
+            return null;
+        }
+
+        let latestIndexedAt: number | null = null;
+        let contentHash: string | null = null;
+        for (const point of points) {
+            const indexedAt = point.payload?.indexedAt as number | undefined;
+            if (indexedAt !== undefined && (latestIndexedAt === null || indexedAt > latestIndexedAt)) {
+                latestIndexedAt = indexedAt;
+                contentHash = (point.payload?.contentHash as string | undefined) ?? contentHash;
+            }
+        }
+
+        return { chunks: points.length, latestIndexedAt, contentHash };
+    }
+
+    async renameFilePath(oldPath: string, newPath: string): Promise<number> {
+        const points = await this.scrollByFilePath(oldPath, false);
+        if (points.length === 0) {
+            return 0;
+        }
+
+        await this.client.setPayload(this.collectionName, {
+            payload: {
+                filePath: normalizeVaultPath(newPath),
+                filePathKey: toPathKey(newPath),
+                folders: getAncestorFolders(newPath),
             },
+            points: points.map((point) => point.id),
+            wait: true,
         });
+
+        console.log(`[Qdrant] Renamed ${points.length} points: ${oldPath} -> ${newPath}`);
+        return points.length;
+    }
+
+    async getByFilePath(filePath: string): Promise<SearchResult[]> {
+        const points = await this.scrollByFilePath(filePath, true);
+
+        return points
+            .map((point) => ({
+                id: (point.payload?._customId as string) || String(point.id),
+                score: 1,
+                metadata: point.payload || {},
+            }))
+            .sort((a, b) => (a.metadata.start_line ?? 0) - (b.metadata.start_line ?? 0));
+    }
+
+    /**
+     * Scroll every point stored for a file, following pagination
+     */
+    private async scrollByFilePath(
+        filePath: string,
+        withPayload: boolean | string[]
+    ): Promise<Array<{ id: string | number; payload?: Record<string, any> | null }>> {
+        const points: Array<{ id: string | number; payload?: Record<string, any> | null }> = [];
+        let offset: string | number | undefined = undefined;
+
+        do {
+            const result: any = await this.client.scroll(this.collectionName, {
+                limit: 256,
+                offset,
+                with_payload: withPayload,
+                with_vector: false,
+                filter: {
+                    must: [this.filePathCondition(filePath)],
+                },
+            });
+
+            points.push(...(result.points || []));
+            offset = result.next_page_offset ?? undefined;
+        } while (offset !== undefined && offset !== null);
+
+        return points;
+    }
+
+    /**
+     * Delete all points matching a payload filter
+     * Returns the number of points that matched before deletion
+     */
+    async deleteByFilter(filter: Record<string, any>): Promise<number> {
+        const { count } = await this.client.count(this.collectionName, {
+            filter,
+            exact: true,
+        });
+
+        if (count > 0) {
+            await this.client.delete(this.collectionName, { filter });
+        }
+
+        console.log(`[Qdrant] Deleted ${count} points by filter`);
+        return count;
+    }
+
+    async deleteByType(type: PointType): Promise<number> {
+        return this.deleteByFilter({
+            must: [{ key: 'type', match: { value: type } }],
+        });
+    }
+
+    async deleteByTag(tag: string): Promise<number> {
+        const normalized = tag.trim().replace(/^#/, '');
+        if (!normalized) {
+            return 0;
+        }
+
+        return this.deleteByFilter({
+            must: [{ key: 'tags', match: { value: normalized } }],
+        });
+    }
+
+    /**
+     * Uses Qdrant's facet API, which needs the field's keyword payload index
+     */
+    async facetCounts(field: FacetField, topK: number): Promise<FacetCount[]> {
+        const { hits } = await this.client.facet(this.collectionName, {
+            key: field,
+            limit: topK,
+            exact: true,
+        });
+        return sortFacets(hits.map((hit) => ({ value: String(hit.value), count: hit.count })));
     }
 
     async count(): Promise<number> {
         try {
             const info = await this.client.getCollection(this.collectionName);
-            return info.points_count || 0;
+            return (await this.resolvePointsCount(info.points_count)) ?? 0;
         } catch (error) {
             return 0;
         }
     }
 
-    async clear(): Promise<void> {
+    /**
+     * `points_count` is approximate and may be missing (e.g. while segments are
+     * being optimized); fall back to an exact count request so "unknown" is not
+     * reported as "empty". Returns null only if the exact count also fails.
+     */
+    private async resolvePointsCount(pointsCount: number | null | undefined): Promise<number | null> {
+        if (typeof pointsCount === 'number') {
+            return pointsCount;
+        }
+
+        try {
+            const { count } = await this.client.count(this.collectionName, { exact: true });
+            return count;
+        } catch (error) {
+            console.warn('[Qdrant] Exact count failed:', error);
+            return null;
+        }
+    }
+
+    async getHealth(): Promise<BackendHealth> {
+        const health: BackendHealth = {
+            connected: false,
+            collectionName: this.collectionName,
+            dimension: null,
+            pointsCount: 0,
+        };
+
+        try {
+            const { collections } = await this.client.getCollections();
+            health.connected = true;
+
+            if (collections.some((c) => c.name === this.collectionName)) {
+                const info = await this.client.getCollection(this.collectionName);
+                const vectors = info.config?.params?.vectors as Record<string, { size?: number }> | undefined;
+                health.dimension = vectors?.[VECTOR_NAMES.CONTENT]?.size ?? null;
+                health.pointsCount = await this.resolvePointsCount(info.points_count);
+            }
+        } catch (error) {
+            console.warn('[Qdrant] Health check failed:', error);
+        }
+
+        return health;
+    }
+
+    /**
+     * Re-apply the optimizer indexing threshold, which makes Qdrant re-check its segments
+     * and index any that grew past it (e.g. after an import)
+     */
+    async optimize(): Promise<string | null> {
+        await this.client.updateCollection(this.collectionName, {
+            optimizers_config: {
+                indexing_threshold: this.tuning?.indexingThreshold ?? DEFAULT_QDRANT_TUNING_CONFIG.indexingThreshold,
+            },
+        });
+
+        const info = await this.client.getCollection(this.collectionName);
+        return info.status ?? null;
+    }
+
+    async exportPoints(
+        write: (line: string) => void | Promise<void>,
+        options: { includeVectors?: boolean } = {}
+    ): Promise<number> {
+        let written = 0;
+        let offset: string | number | undefined = undefined;
+
+        // Page through the collection so large indexes are never held in memory at once
+        do {
+            const result: any = await this.client.scroll(this.collectionName, {
+                limit: 256,
+                offset,
+                with_payload: true,
+                with_vector: options.includeVectors ?? false,
+            });
+
+            for (const point of result.points || []) {
+                const { _customId, ...payload } = point.payload || {};
+                const exported = toExportedPoint(
+                    String(_customId ?? point.id),
+                    payload,
+                    options.includeVectors ? point.vector : undefined,
+                    point.id
+                );
+                await write(JSON.stringify(exported));
+                written++;
+            }
+            offset = result.next_page_offset ?? undefined;
+        } while (offset !== undefined && offset !== null);
+
+        return written;
+    }
+
+    async importPoints(lines: string[], options: { recreate?: boolean } = {}): Promise<ImportReport> {
+        const report: ImportReport = { imported: 0, skipped: 0 };
+        const items: ImportedPoint[] = [];
+        for (const line of lines) {
+            if (!line.trim()) {
+                continue;
+            }
+            const item = parseExportedLine(line);
+            if (item) {
+                items.push(item);
+            } else {
+                report.skipped++;
+            }
+        }
+        if (items.length === 0) {
+            return report;
+        }
+
+        // Collection dimension: the first point's when recreating or creating, otherwise the existing one
+        const firstDimension = pointDimension(items[0]) ?? 0;
+        if (options.recreate) {
+            await this.clear(firstDimension);
+        }
+        let collection = await this.readCollectionVectors();
+        if (!collection) {
+            await this.ensureCollection(firstDimension);
+            collection = await this.readCollectionVectors();
+        }
+        const dimension = collection?.dimension ?? firstDimension;
+        const names = new Set(collection?.names ?? []);
+        this.vectorSize = dimension;
+
+        // Keep only the vectors the collection has; points left without any are skipped
+        const points = items
+            .filter((item) => pointDimension(item) === dimension)
+            .map((item) => {
+                const vector: Record<string, number[] | number[][]> = {};
+                Object.keys(item.vectors)
+                    .filter((name) => names.has(name))
+                    .forEach((name) => {
+                        vector[name] = item.vectors[name];
+                    });
+                if (item.subVectors && names.has(CONTENT_MULTI_VECTOR)) {
+                    vector[CONTENT_MULTI_VECTOR] = item.subVectors;
+                }
+                const id = importedPointId(item);
+                return {
+                    id,
+                    vector,
+                    payload: String(id) === item.id ? item.metadata : { ...item.metadata, _customId: item.id },
+                };
+            })
+            .filter((point) => Object.keys(point.vector).length > 0);
+        report.skipped += items.length - points.length;
+
+        for (let i = 0; i < points.length; i += IMPORT_BATCH_SIZE) {
+            const batch = points.slice(i, i + IMPORT_BATCH_SIZE);
+            await this.client.upsert(this.collectionName, { wait: true, points: batch });
+            report.imported += batch.length;
+        }
+
+        console.log(`[Qdrant] Imported ${report.imported} points, skipped ${report.skipped}`);
+        return report;
+    }
+
+    /**
+     * Vector names and content dimension of the existing collection, null if it doesn't exist
+     */
+    private async readCollectionVectors(): Promise<{ names: string[]; dimension: number | null } | null> {
+        let collection: Schemas['CollectionInfo'];
+        try {
+            collection = await this.client.getCollection(this.collectionName);
+        } catch {
+            return null;
+        }
+
+        const vectors = (collection.config?.params?.vectors ?? {}) as Record<string, { size?: number }>;
+        this.collectionHasMultiVector = hasMultiVector(collection);
+        return {
+            names: Object.keys(vectors),
+            dimension: vectors[VECTOR_NAMES.CONTENT]?.size ?? null,
+        };
+    }
+
+    async reembedPoints(
+        embed: (metadata: Record<string, any>) => Promise<Pick<MultiVectorItem, 'vectors' | 'subVectors'>>,
+        onProgress?: (progress: ReembedReport) => void
+    ): Promise<ReembedReport> {
+        const report: ReembedReport = { updated: 0, failed: 0 };
+        let offset: string | number | undefined = undefined;
+        await this.readCollectionVectors();
+
+        do {
+            const result: any = await this.client.scroll(this.collectionName, {
+                limit: REEMBED_PAGE_SIZE,
+                offset,
+                with_payload: true,
+                with_vector: false,
+                filter: { must_not: [{ key: 'type', match: { value: 'concept' } }] },
+            });
+
+            const updates: Array<{ id: string | number; vector: Record<string, number[] | number[][]> }> = [];
+            const withoutSubVectors: Array<string | number> = [];
+            for (const point of result.points || []) {
+                const { _customId, ...payload } = point.payload || {};
+                try {
+                    const { vectors, subVectors } = await embed(payload);
+                    const hasSubVectors = this.collectionHasMultiVector && Boolean(subVectors?.length);
+                    updates.push({
+                        id: point.id,
+                        vector: hasSubVectors ? { ...vectors, [CONTENT_MULTI_VECTOR]: subVectors ?? [] } : vectors,
+                    });
+                    if (this.collectionHasMultiVector && !hasSubVectors) {
+                        withoutSubVectors.push(point.id);
+                    }
+                } catch (error) {
+                    console.warn('[Qdrant] Re-embedding failed for', _customId ?? point.id, error);
+                    report.failed++;
+                }
+            }
+
+            // Only the chunk vectors are replaced; payload and point ids stay as they are
+            if (updates.length > 0) {
+                await this.client.updateVectors(this.collectionName, { wait: true, points: updates });
+                report.updated += updates.length;
+            }
+            // Sub-vectors from the old model would mix embedding spaces in max-sim search
+            if (withoutSubVectors.length > 0) {
+                await this.client.deleteVectors(this.collectionName, {
+                    wait: true,
+                    points: withoutSubVectors,
+                    vector: [CONTENT_MULTI_VECTOR],
+                });
+            }
+            onProgress?.({ ...report });
+            offset = result.next_page_offset ?? undefined;
+        } while (offset !== undefined && offset !== null);
+
+        return report;
+    }
+
+    async clear(dimension?: number): Promise<void> {
         try {
             await this.client.deleteCollection(this.collectionName);
-            this.vectorSize = null;
         } catch (error) {
             // Collection might not exist, ignore
         }
+        this.vectorSize = null;
+
+        if (dimension !== undefined) {
+            await this.ensureCollection(dimension);
+            this.vectorSize = dimension;
+        }
     }
 
     async ensureReady(): Promise<void> {
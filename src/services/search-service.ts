@@ -3,55 +3,372 @@
  * v0.7.0: Focused on searching, independent of Concept/Index services
  */
 
-import type { VectorBackend } from './vector-backend';
+import type { VectorBackend, PointType } from './vector-backend';
 import { EmbeddingService } from './embedding-service';
+import { buildPreview, buildSnippet, significantTokens, truncateChars } from '@utils/text-utils';
+import { normalizeVaultPath } from '@utils/path-utils';
+import { cosineSimilarity } from '@utils/vector-utils';
+import type { ScoreCalibration, ScoreTransform, SearchConfig } from '@core/types/setting';
+import { DEFAULT_SEARCH_CONFIG } from '@core/types/setting';
 
 export interface SearchResult {
+    pointId?: string; // Backend point id, e.g. for excluding sources in searchByVectors
     notePath: string;
     title: string;
-    similarity: number;
+    noteTitle?: string; // Frontmatter title, first H1 or file name
+    similarity: number; // Display score, after `scoreTransform`
+    rawScore?: number; // Untransformed backend score: an RRF fusion score (roughly 0.01–0.05), not a cosine similarity
+    weakQuery?: boolean; // Query had too few significant tokens; results came from a widened search
     excerpt?: string;
+    preview?: string;
+    snippet?: string; // Best-matching sentence with surrounding context, see `snippetLength`
+    vector?: number[];
+    debug?: ResultDebug;
 }
 
+// Diagnostics attached to each result when `debug` is requested
+export interface ResultDebug {
+    pointId: string;
+    rawScore: number; // Fused backend score, before any post-processing
+    backendRank: number; // 1-based position in the backend result list
+    filters: {
+        excludePath?: string;
+        pathPrefix?: string;
+        folder?: string;
+        type?: PointType;
+        since?: number;
+        until?: number;
+        minContentLength?: number;
+        minDepth?: number;
+        maxDepth?: number;
+    };
+}
+
+export interface SearchQueryOptions {
+    pathPrefix?: string; // Only return notes under this folder, e.g. "projects/alpha/"
+    folder?: string; // Like pathPrefix but filtered in the backend via the `folders` payload, e.g. "projects"
+    previewLength?: number; // Fill `preview` with a sentence-aligned excerpt of this many chars
+    snippetLength?: number; // Fill `snippet` with the sentence sharing most words with the query, widened to this many chars
+    withVectors?: boolean; // Include stored content vectors, e.g. for client-side reranking
+    since?: number; // Only chunks indexed at or after this time (ms since epoch)
+    until?: number; // Only chunks indexed at or before this time (ms since epoch)
+    minContentLength?: number; // Drop chunks shorter than this many characters (code points)
+    debug?: boolean; // Attach a `debug` explanation to each result
+    scoreTransform?: ScoreTransform; // Overrides the configured transform for this query
+    type?: PointType; // Only points of this type, overrides the configured defaultPointType
+    typeWeights?: Partial<Record<PointType, number>>; // Multiply scores by their point type's weight (default 1) before sorting
+    recencyBoost?: number; // Overrides the configured boost: add boost * 0.5^(age / half-life) to each score relative to the best match (0–1), then re-sort
+    minDepth?: number; // Only chunks at least this deep in the heading tree (1 = under a top-level heading)
+    maxDepth?: number; // Only chunks at most this deep, e.g. 1 for top-level sections
+    calibrated?: boolean; // Map raw scores to 0–1 relevance with the stored calibration instead of `scoreTransform`, then apply boosts
+}
+
+// A known-relevant note for a query, used to calibrate scores
+export interface CalibrationPair {
+    query: string;
+    relevantPath: string;
+}
+
+export interface SearchResultGroup {
+    notePath: string;
+    topScore: number;
+    matches: SearchResult[];
+}
+
+const DAY_MS = 24 * 60 * 60 * 1000;
+
+// Limit multiplier for weak queries with the "widen" behavior
+const WEAK_QUERY_WIDEN = 3;
+
+// Excerpt length (code points) when a chunk has no summary
+const EXCERPT_CHARS = 100;
+
+// Over-fetch factor for grouped search, so each note can collect several chunks
+const GROUP_OVERFETCH = 5;
+
+// Results per calibration query; the relevant note's chunks are positives, the rest negatives
+const CALIBRATION_LIMIT = 20;
+
+// Logit at the positive/negative mean scores (±2 maps to about 0.88 and 0.12)
+const CALIBRATION_SPREAD = 2;
+
+// Over-fetch factor when post-filtering or re-ranking (path prefix, content length, recency, type weights)
+const POST_FILTER_OVERFETCH = 4;
+
 export class SearchService {
+    private config: SearchConfig;
+
     constructor(
         private embeddingService: EmbeddingService,
-        private vectorBackend: VectorBackend
-    ) {}
+        private vectorBackend: VectorBackend,
+        config: Partial<SearchConfig> = DEFAULT_SEARCH_CONFIG
+    ) {
+        this.config = { ...DEFAULT_SEARCH_CONFIG, ...config };
+    }
+
+    /**
+     * Update search configuration
+     */
+    updateConfig(config: Partial<SearchConfig>): void {
+        this.config = { ...this.config, ...config };
+    }
 
     /**
      * Search for related notes based on query text
      * @param query - Search query (concept/summary/title)
      * @param excludePath - File path to exclude (e.g., current file)
-     * @param limit - Number of results to return
+     * @param limit - Number of results to return, capped at the configured maxResults
+     * @param options - Extra filters, combinable with excludePath
      */
     async search(
         query: string,
         excludePath?: string,
-        limit: number = 10
+        limit: number = 10,
+        options: SearchQueryOptions = {}
+    ): Promise<SearchResult[]> {
+        return this.searchChunks(query, excludePath, limit, options, this.config.maxResults);
+    }
+
+    /**
+     * search() with the result cap as a parameter, so internal over-fetches aren't clipped
+     * by the user-facing maxResults
+     */
+    private async searchChunks(
+        query: string,
+        excludePath: string | undefined,
+        limit: number,
+        options: SearchQueryOptions,
+        maxResults: number
     ): Promise<SearchResult[]> {
+        // Nothing to match: skip the embedding call
+        query = query.trim();
+        if (!query) {
+            return [];
+        }
+        limit = Math.min(limit, maxResults);
+
+        // Stop-word-only queries embed to something that matches everything a little
+        const { minQueryTokens, stopWords, weakQueryBehavior } = this.config;
+        const weakQuery = minQueryTokens > 0 && significantTokens(query, stopWords).length < minQueryTokens;
+        if (weakQuery) {
+            if (weakQueryBehavior === 'empty') {
+                return [];
+            }
+            limit = Math.min(limit * WEAK_QUERY_WIDEN, maxResults);
+        }
+
+        const calibration = options.calibrated ? this.config.calibration : undefined;
+        if (options.calibrated && !calibration) {
+            throw new Error('Scores are not calibrated, run calibrate() first');
+        }
+
+        const { previewLength, snippetLength, withVectors, since, until, folder, minContentLength, minDepth, maxDepth, debug } = options;
+        const recencyBoost = options.recencyBoost ?? this.config.recencyBoost;
+        const type = options.type ?? this.config.defaultPointType;
+        const pathPrefix = options.pathPrefix && normalizeVaultPath(options.pathPrefix);
+        if (excludePath) {
+            excludePath = normalizeVaultPath(excludePath);
+        }
+
         // 1. Vectorize query
         const queryVector = await this.embeddingService.embed(query);
 
         // 2. Search using vector backend's searchWithFusion method
+        // Qdrant keyword payloads have no prefix/length match, so over-fetch and post-filter
+        const typeWeights = options.typeWeights;
+        const postFiltered = Boolean(pathPrefix || minContentLength || recencyBoost || typeWeights);
         const backendResults = await this.vectorBackend.searchWithFusion(queryVector, {
-            limit,
-            filter: excludePath ? { tags: undefined } : undefined // Note: current backend doesn't support path filtering
+            limit: postFiltered ? limit * POST_FILTER_OVERFETCH : limit,
+            withVectors,
+            filter: [since, until, minDepth, maxDepth].some(bound => bound !== undefined) || folder || type || excludePath
+                ? { since, until, folder, type, excludeFilePath: excludePath, minDepth, maxDepth }
+                : undefined
         });
 
+        const filters = { excludePath, pathPrefix, folder, type, since, until, minContentLength, minDepth, maxDepth };
+        const now = Date.now();
+        const halfLifeMs = this.config.recencyHalfLifeDays * DAY_MS;
+        // Calibration was fitted on raw backend scores, so it maps those; boosts apply on top.
+        // Uncalibrated RRF scores are tiny (about 0.01–0.05), so the recency boost is added to
+        // each score relative to the query's best match instead, on the same 0–1 scale as the boost
+        const topScore = Math.max(0, ...backendResults.map(r => r.score));
+        const baseScore = (score: number) => calibration
+            ? applyCalibration(score, calibration)
+            : recencyBoost && topScore > 0 ? score / topScore : score;
+
         // 3. Filter out excluded path and format results
         const results = backendResults
+            .map((r, i) => ({ ...r, backendRank: i + 1 }))
             .filter(r => r.metadata.filePath !== excludePath)
+            .filter(r => !pathPrefix || r.metadata.filePath?.startsWith(pathPrefix))
+            .filter(r => !minContentLength || Array.from(r.metadata.content ?? '').length >= minContentLength)
+            .map(r => ({
+                pointId: r.id,
+                notePath: r.metadata.filePath,
+                title: r.metadata.header_path || r.metadata.filePath.split('/').pop() || r.metadata.filePath,
+                ...(r.metadata.title && { noteTitle: r.metadata.title }),
+                similarity: (recencyBoost
+                    ? baseScore(r.score) + recencyBoost * recencyWeight(r.metadata.indexedAt, now, halfLifeMs)
+                    : baseScore(r.score)) * (typeWeights?.[r.metadata.type as PointType] ?? 1),
+                rawScore: r.score,
+                ...(weakQuery && { weakQuery }),
+                excerpt: r.metadata.summary || (r.metadata.content && truncateChars(r.metadata.content, EXCERPT_CHARS)),
+                ...(previewLength && r.metadata.content && {
+                    preview: buildPreview(r.metadata.content, previewLength)
+                }),
+                ...(snippetLength && r.metadata.content && {
+                    snippet: buildSnippet(r.metadata.content, query, snippetLength, stopWords)
+                }),
+                ...(r.vector && { vector: r.vector }),
+                ...(debug && {
+                    debug: { pointId: r.id, rawScore: r.score, backendRank: r.backendRank, filters }
+                })
+            }))
+            .sort((a, b) => b.similarity - a.similarity) // Descending by similarity
+            .slice(0, limit);
+
+        if (calibration) {
+            return results;
+        }
+        return applyScoreTransform(results, options.scoreTransform ?? this.config.scoreTransform);
+    }
+
+    /**
+     * Fit a score calibration from queries with a known relevant note
+     * Scores of the relevant note's chunks count as positives, every other result as a negative.
+     * The fit is on raw RRF fusion scores, not cosine similarities, so refit after changing the
+     * embedding model or fusion weights. Returned, not applied: save it with
+     * `settingsManager.updateSearch({ calibration })`, which also hands it to this service
+     */
+    async calibrate(pairs: CalibrationPair[]): Promise<ScoreCalibration> {
+        const positives: number[] = [];
+        const negatives: number[] = [];
+
+        for (const pair of pairs) {
+            const relevantPath = normalizeVaultPath(pair.relevantPath);
+            const queryVector = await this.embeddingService.embed(pair.query);
+            const results = await this.vectorBackend.searchWithFusion(queryVector, { limit: CALIBRATION_LIMIT });
+            for (const r of results) {
+                (r.metadata.filePath === relevantPath ? positives : negatives).push(r.score);
+            }
+        }
+
+        return fitScoreCalibration(positives, negatives);
+    }
+
+    /**
+     * "More like these": search around the centroid of several liked result vectors
+     * @param vectors - Content vectors of the liked chunks (see `withVectors`)
+     * @param excludeIds - Point ids of the source chunks, left out of the results
+     */
+    async searchByVectors(
+        vectors: number[][],
+        limit: number = 10,
+        excludeIds: string[] = []
+    ): Promise<SearchResult[]> {
+        if (vectors.length === 0) {
+            return [];
+        }
+
+        const dimension = vectors[0].length;
+        const centroid = new Array<number>(dimension).fill(0);
+        for (const vector of vectors) {
+            if (vector.length !== dimension) {
+                throw new Error(`Vector dimension mismatch: expected ${dimension}, got ${vector.length}`);
+            }
+            for (let i = 0; i < dimension; i++) {
+                centroid[i] += vector[i] / vectors.length;
+            }
+        }
+
+        const excluded = new Set(excludeIds);
+        const backendResults = await this.vectorBackend.searchWithFusion(centroid, {
+            limit: limit + excluded.size,
+        });
+
+        return backendResults
+            .filter(r => !excluded.has(r.id))
             .slice(0, limit)
             .map(r => ({
+                pointId: r.id,
                 notePath: r.metadata.filePath,
                 title: r.metadata.header_path || r.metadata.filePath.split('/').pop() || r.metadata.filePath,
+                ...(r.metadata.title && { noteTitle: r.metadata.title }),
                 similarity: r.score,
-                excerpt: r.metadata.summary || r.metadata.content?.slice(0, 100)
-            }))
-            .sort((a, b) => b.similarity - a.similarity); // Descending by similarity
+                excerpt: r.metadata.summary || (r.metadata.content && truncateChars(r.metadata.content, EXCERPT_CHARS)),
+            }));
+    }
 
-        return results;
+    /**
+     * Search within earlier results: re-rank only these candidates by cosine similarity to a new query
+     * Candidates must carry their stored vectors (search with `withVectors`); no backend search is made
+     */
+    async refine(query: string, candidates: SearchResult[]): Promise<SearchResult[]> {
+        query = query.trim();
+        if (!query || candidates.length === 0) {
+            return [];
+        }
+        if (candidates.some(c => !c.vector)) {
+            throw new Error('Refine needs candidate vectors, search with withVectors first');
+        }
+
+        const queryVector = await this.embeddingService.embed(query);
+        return candidates
+            .map(c => {
+                const score = cosineSimilarity(queryVector, c.vector!);
+                return { ...c, similarity: score, rawScore: score };
+            })
+            .sort((a, b) => b.similarity - a.similarity);
+    }
+
+    /**
+     * Get all indexed chunks of a note in document order, without embedding a query
+     */
+    async getByPath(notePath: string): Promise<SearchResult[]> {
+        notePath = normalizeVaultPath(notePath);
+        const chunks = await this.vectorBackend.getByFilePath(notePath);
+
+        return chunks.map(r => ({
+            notePath: r.metadata.filePath,
+            title: r.metadata.header_path || notePath.split('/').pop() || notePath,
+            ...(r.metadata.title && { noteTitle: r.metadata.title }),
+            similarity: r.score,
+            excerpt: r.metadata.summary || (r.metadata.content && truncateChars(r.metadata.content, EXCERPT_CHARS)),
+        }));
+    }
+
+    /**
+     * Search and group matching chunks by note, sorted by each note's best score
+     * @param limit - Number of groups (notes) to return, capped at the configured maxResults
+     */
+    async searchGrouped(
+        query: string,
+        excludePath?: string,
+        limit: number = 10,
+        options: SearchQueryOptions = {}
+    ): Promise<SearchResultGroup[]> {
+        // The cap applies to groups; the chunk over-fetch behind them is uncapped
+        limit = Math.min(limit, this.config.maxResults);
+        const results = await this.searchChunks(query, excludePath, limit * GROUP_OVERFETCH, options, Infinity);
+
+        const groups = new Map<string, SearchResultGroup>();
+        for (const result of results) {
+            const group = groups.get(result.notePath);
+            if (group) {
+                group.matches.push(result);
+                group.topScore = Math.max(group.topScore, result.similarity);
+            } else {
+                groups.set(result.notePath, {
+                    notePath: result.notePath,
+                    topScore: result.similarity,
+                    matches: [result],
+                });
+            }
+        }
+
+        return Array.from(groups.values())
+            .sort((a, b) => b.topScore - a.topScore)
+            .slice(0, limit);
     }
 
     /**
@@ -67,3 +384,63 @@ export class SearchService {
         return this.search(query, currentFilePath, limit);
     }
 }
+
+/**
+ * 1 for a chunk indexed now, halving every half-life; 0 when the index time is unknown
+ */
+function recencyWeight(indexedAt: number | undefined, now: number, halfLifeMs: number): number {
+    if (typeof indexedAt !== 'number' || halfLifeMs <= 0) {
+        return 0;
+    }
+    return Math.pow(0.5, Math.max(0, now - indexedAt) / halfLifeMs);
+}
+
+/**
+ * Logistic calibration placing the midpoint between the mean positive and negative score at 0.5
+ * Throws when positives do not score higher than negatives on average (nothing to calibrate against)
+ */
+export function fitScoreCalibration(positives: number[], negatives: number[]): ScoreCalibration {
+    if (positives.length === 0 || negatives.length === 0) {
+        throw new Error('Calibration needs both relevant and non-relevant results');
+    }
+
+    const mean = (scores: number[]) => scores.reduce((a, b) => a + b, 0) / scores.length;
+    const positiveMean = mean(positives);
+    const negativeMean = mean(negatives);
+    if (positiveMean <= negativeMean) {
+        throw new Error('Relevant results do not score higher than the rest, cannot calibrate');
+    }
+
+    const slope = (2 * CALIBRATION_SPREAD) / (positiveMean - negativeMean);
+    return { slope, intercept: -slope * (positiveMean + negativeMean) / 2 };
+}
+
+/**
+ * Relevance probability in [0, 1] for a raw score
+ */
+export function applyCalibration(score: number, calibration: ScoreCalibration): number {
+    return 1 / (1 + Math.exp(-(calibration.slope * score + calibration.intercept)));
+}
+
+/**
+ * Spread a sorted result page's scores over 0–1 for display, leaving `rawScore` untouched
+ * - minmax: best result 1, worst 0 (all 1 when every score is equal)
+ * - rank: evenly spaced by position, best 1
+ */
+export function applyScoreTransform(results: SearchResult[], transform: ScoreTransform): SearchResult[] {
+    if (transform === 'raw' || results.length === 0) {
+        return results;
+    }
+
+    if (transform === 'rank') {
+        return results.map((r, i) => ({ ...r, similarity: (results.length - i) / results.length }));
+    }
+
+    const scores = results.map(r => r.similarity);
+    const max = Math.max(...scores);
+    const min = Math.min(...scores);
+    return results.map(r => ({
+        ...r,
+        similarity: max === min ? 1 : (r.similarity - min) / (max - min),
+    }));
+}
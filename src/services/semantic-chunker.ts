@@ -1,6 +1,7 @@
 import { BaseModelConfig } from "@core/types/setting";
 import type { SemanticChunk } from "@core/types/indexing";
 import { Chunker } from "./chunker";
+import { parseModelJson } from "@utils/json-utils";
 
 export class SemanticChunker {
 	private config: BaseModelConfig;
@@ -67,7 +68,7 @@ export class SemanticChunker {
 		}
 
 		const data = await response.json();
-		return JSON.parse(data.response || "{}");
+		return parseModelJson(data.response || "{}", "Ollama");
 	}
 
 	private async extractWithOpenAI(
@@ -104,9 +105,7 @@ export class SemanticChunker {
 		}
 
 		const data = await response.json();
-		const contentStr = data.choices[0]?.message?.content || "{}";
-		const cleanJson = contentStr.replace(/```json\n?|\n?```/g, "");
-		return JSON.parse(cleanJson);
+		return parseModelJson(data.choices[0]?.message?.content || "{}", "OpenAI");
 	}
 
 	private buildPrompt(numberedContent: string, title: string): string {
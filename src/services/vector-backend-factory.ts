@@ -0,0 +1,33 @@
+/**
+ * Build the vector backend selected in settings
+ */
+
+import { QdrantBackend } from './qdrant-backend';
+import type { QdrantConnectionOptions, EmbeddingServiceDimension } from './qdrant-backend';
+import { MemoryBackend } from './memory-backend';
+import type { QdrantTuningConfig, VectorBackendKind } from '@core/types/setting';
+
+export interface VectorBackendOptions {
+    kind: VectorBackendKind;
+    collectionName: string;
+    qdrantUrl: string;
+    tuning: QdrantTuningConfig;
+    connection: QdrantConnectionOptions;
+    embeddingService: EmbeddingServiceDimension; // Qdrant checks the collection dimension against a probe embedding
+}
+
+/**
+ * Both backends also implement the concept registry methods
+ */
+export function createVectorBackend(options: VectorBackendOptions): QdrantBackend | MemoryBackend {
+    if (options.kind === 'memory') {
+        return new MemoryBackend(options.collectionName);
+    }
+    return new QdrantBackend(
+        options.collectionName,
+        options.qdrantUrl,
+        options.embeddingService,
+        options.tuning,
+        options.connection
+    );
+}
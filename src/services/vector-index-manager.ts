@@ -5,10 +5,20 @@
 
 import { MemoryCache, CachedChunk } from "./memory-cache";
 import { PersistQueue, MultiVectorQueuedChunk } from "./persist-queue";
-import type { VectorBackend, SearchResult } from "./vector-backend";
+import type {
+	VectorBackend,
+	SearchResult,
+	ReembedReport,
+} from "./vector-backend";
+import { compareSearchResults } from "./vector-backend";
 import { VECTOR_NAMES } from "@core/constants";
 import { EmbeddingService } from "./embedding-service";
-import { Chunker, ChunkResult } from "./chunker";
+import {
+	Chunker,
+	ChunkResult,
+	createChunkStrategy,
+	getCalloutLabels,
+} from "./chunker";
 import { MetadataExtractor } from "./metadata-extractor";
 import { ContentPreprocessor } from "./content-preprocessor";
 import { SemanticChunker } from "./semantic-chunker";
@@ -16,7 +26,62 @@ import type {
 	ExtractedConceptDetail,
 	ExtractedMetadataConcept,
 } from "@core/types/extraction";
-import type { SemanticChunk, UnifiedIndexResult } from "@core/types/indexing";
+import type {
+	BatchIndexProgress,
+	BatchIndexReport,
+	ChunkStrategyName,
+	IndexFileInput,
+	IndexFileReport,
+	IndexProgress,
+	SemanticChunk,
+	UnifiedIndexResult,
+} from "@core/types/indexing";
+import type { IndexingConfig } from "@core/types/setting";
+import { DEFAULT_INDEXING_CONFIG } from "@core/types/setting";
+import {
+	getAncestorFolders,
+	getFileKind,
+	normalizeVaultPath,
+	toPathKey,
+} from "@utils/path-utils";
+import { getErrorMessage } from "@utils/error";
+import { cosineSimilarity } from "@utils/vector-utils";
+import { generateUUID } from "@utils/uuid";
+import {
+	extractTitle,
+	hashContent,
+	normalizeNewlines,
+	stripMarkdown,
+	truncateChars,
+} from "@utils/text-utils";
+
+/**
+ * Thrown when indexing a single file exceeds `indexTimeoutMs`
+ */
+export class IndexTimeoutError extends Error {
+	constructor(
+		public readonly filePath: string,
+		public readonly timeoutMs: number,
+	) {
+		super(`索引超时 (${timeoutMs}ms): ${filePath}`);
+		this.name = "IndexTimeoutError";
+	}
+}
+
+// Paragraph vectors embedded per chunk at most with `multiVector`
+const MAX_SUB_VECTORS = 16;
+
+// Set once an index run has timed out, so in-flight chunks are discarded
+interface IndexAbortSignal {
+	aborted: boolean;
+}
+
+// Per-note fields shared by every point of a file
+interface NoteInfo {
+	title: string;
+	contentHash?: string;
+	indexRunId?: string; // Tags the points of one indexFile run
+}
 
 export class VectorIndexManager {
 	private memoryCache: MemoryCache;
@@ -27,6 +92,7 @@ export class VectorIndexManager {
 	private metadataExtractor: MetadataExtractor;
 	private contentPreprocessor: ContentPreprocessor;
 	private semanticChunker: SemanticChunker;
+	private config: IndexingConfig;
 
 	constructor(
 		backend: VectorBackend,
@@ -36,6 +102,7 @@ export class VectorIndexManager {
 		contentPreprocessor: ContentPreprocessor,
 		semanticChunker: SemanticChunker,
 		cacheSize: number = 50 * 1024 * 1024, // 50MB default
+		config: IndexingConfig = DEFAULT_INDEXING_CONFIG,
 	) {
 		this.backend = backend;
 		this.embeddingService = embeddingService;
@@ -43,31 +110,178 @@ export class VectorIndexManager {
 		this.metadataExtractor = metadataExtractor;
 		this.contentPreprocessor = contentPreprocessor;
 		this.semanticChunker = semanticChunker;
+		this.config = { ...DEFAULT_INDEXING_CONFIG, ...config };
 
 		this.memoryCache = new MemoryCache(cacheSize);
 		this.persistQueue = new PersistQueue(backend, {
-			batchSize: 50,
+			batchSize: this.config.persistBatchSize,
 			flushInterval: 30000,
 			useMultiVector: true,
 		});
 	}
 
 	/**
-	 * Index a file
+	 * Update indexing configuration
+	 */
+	updateConfig(config: Partial<IndexingConfig>): void {
+		this.config = { ...this.config, ...config };
+		this.persistQueue.setBatchSize(this.config.persistBatchSize);
+	}
+
+	/**
+	 * Index a file, returns the number of chunks indexed
+	 * The file's points are tagged with a fresh `indexRunId`; once every chunk is
+	 * flushed, the points of earlier runs are deleted, so re-indexing replaces the
+	 * previous version instead of adding to it
+	 * @param onProgress - Called after each chunk is embedded
+	 * @param strategy - Chunk strategy for this file, overriding the configured one
+	 */
+	async indexFile(
+		filePath: string,
+		content: string,
+		onProgress?: (progress: IndexProgress) => void,
+		strategy?: ChunkStrategyName,
+	): Promise<number> {
+		const indexRunId = generateUUID();
+		const chunks = await this.runIndex(
+			filePath,
+			content,
+			indexRunId,
+			onProgress,
+			strategy,
+		);
+
+		// The new version is complete and stored before the previous one goes
+		await this.persistQueue.flushMultiVector();
+		await this.backend.deleteByFilePath(filePath, {
+			exceptIndexRunId: indexRunId,
+		});
+		return chunks;
+	}
+
+	/**
+	 * Aborts with IndexTimeoutError after `indexTimeoutMs`, dropping the file's
+	 * cached and queued chunks and deleting the points this run already flushed,
+	 * so no partial index is persisted and the previous run's points stay searchable
 	 */
-	async indexFile(filePath: string, content: string): Promise<void> {
+	private async runIndex(
+		filePath: string,
+		content: string,
+		indexRunId: string,
+		onProgress?: (progress: IndexProgress) => void,
+		strategy?: ChunkStrategyName,
+	): Promise<number> {
+		const timeoutMs = this.config.indexTimeoutMs;
+		if (!timeoutMs || timeoutMs <= 0) {
+			return this.indexFileChunks(
+				filePath,
+				content,
+				indexRunId,
+				onProgress,
+				strategy,
+			);
+		}
+
+		const signal: IndexAbortSignal = { aborted: false };
+		let timer: ReturnType<typeof setTimeout> | undefined;
+		const timeout = new Promise<never>((_, reject) => {
+			timer = setTimeout(() => {
+				signal.aborted = true;
+				reject(new IndexTimeoutError(filePath, timeoutMs));
+			}, timeoutMs);
+		});
+
+		try {
+			return await Promise.race([
+				this.indexFileChunks(
+					filePath,
+					content,
+					indexRunId,
+					onProgress,
+					strategy,
+					signal,
+				),
+				timeout,
+			]);
+		} catch (error) {
+			if (error instanceof IndexTimeoutError) {
+				console.warn("[MemoEcho] Index timed out:", filePath);
+				this.removeFile(filePath);
+				// Auto-flushes may have written some of this run's chunks already
+				try {
+					await this.backend.deleteByFilePath(filePath, { indexRunId });
+				} catch (deleteError) {
+					console.warn(
+						"[MemoEcho] Failed to delete partial index of",
+						filePath,
+						deleteError,
+					);
+				}
+			}
+			throw error;
+		} finally {
+			clearTimeout(timer);
+		}
+	}
+
+	private async indexFileChunks(
+		filePath: string,
+		content: string,
+		indexRunId: string,
+		onProgress?: (progress: IndexProgress) => void,
+		strategy?: ChunkStrategyName,
+		signal?: IndexAbortSignal,
+	): Promise<number> {
 		console.log("[MemoEcho] Index start:", filePath);
 
-		// Chunk the content
-		const chunks = this.chunker.chunk(content);
+		// Chunks with nothing left to embed after preprocessing would become empty points
+		const { chunks: chunked, oversized } = this.applyOversizedPolicy(
+			this.chunkContent(filePath, content, strategy),
+		);
+		const embeddable = chunked.filter(
+			(chunk) => this.embedContent(chunk).trim().length > 0,
+		);
+		const skipped = chunked.length - embeddable.length;
+		const { chunks, capped } = this.capChunks(embeddable);
 		console.log(
 			"[MemoEcho] Chunk count:",
 			chunks.length,
 			"Content length:",
 			content.length,
 		);
+		if (oversized > 0) {
+			console.warn(
+				`[MemoEcho] ${oversized} unbreakable chunk(s) in ${filePath} exceed maxChunkSize, applied policy:`,
+				this.config.oversizedPolicy,
+			);
+		}
+		if (skipped > 0) {
+			console.log(
+				`[MemoEcho] Skipped ${skipped} empty chunk(s) in`,
+				filePath,
+			);
+		}
+		if (chunks.length === 0 && (skipped > 0 || oversized > 0)) {
+			onProgress?.({ embedded: 0, total: 0, capped, skipped, oversized });
+		}
+		if (capped) {
+			console.warn(
+				`[MemoEcho] ${filePath} exceeds ${this.config.maxChunksPerDoc} chunks, applied policy:`,
+				this.config.chunkOverflowPolicy,
+			);
+		}
 
+		const note: NoteInfo = {
+			title: extractTitle(content, filePath),
+			contentHash: this.fileHash(content, strategy),
+			indexRunId,
+		};
+
+		let embedded = 0;
 		for (const chunk of chunks) {
+			if (signal?.aborted) {
+				break;
+			}
 			try {
 				if (chunks.length <= 3 || chunk.index === 0) {
 					console.log(
@@ -79,7 +293,14 @@ export class VectorIndexManager {
 						chunk.header_path || "",
 					);
 				}
-				await this.indexChunk(filePath, chunk);
+				await this.indexChunk(filePath, chunk, note, signal);
+				onProgress?.({
+					embedded: ++embedded,
+					total: chunks.length,
+					capped,
+					skipped,
+					oversized,
+				});
 			} catch (error) {
 				console.error(
 					"[MemoEcho] Failed indexing chunk",
@@ -91,7 +312,254 @@ export class VectorIndexManager {
 				throw error;
 			}
 		}
+
+		if (this.config.indexHeadings) {
+			for (let i = 0; i < chunks.length; i++) {
+				if (signal?.aborted) {
+					break;
+				}
+				const sectionStart =
+					i === 0 || chunks[i - 1].header_path !== chunks[i].header_path;
+				if (sectionStart) {
+					await this.indexHeading(filePath, chunks[i], note, signal);
+				}
+			}
+		}
 		console.log("[MemoEcho] Index finished:", filePath);
+		return chunks.length;
+	}
+
+	/**
+	 * Chunk with the requested strategy, else "fixed" for plain-text files
+	 * (no header parsing) and the configured strategy for Markdown
+	 */
+	private chunkContent(
+		filePath: string,
+		content: string,
+		strategy?: ChunkStrategyName,
+	): ChunkResult[] {
+		const name =
+			strategy ??
+			(getFileKind(filePath, this.config.plainTextExtensions) ===
+			"plaintext"
+				? "fixed"
+				: (this.config.chunkStrategy ?? "header"));
+		return createChunkStrategy(name, this.chunker).chunk(content);
+	}
+
+	/**
+	 * Hash of a file's content plus every setting that shapes its points,
+	 * stored as `contentHash` so a changed setting re-indexes the file too
+	 */
+	private fileHash(content: string, strategy?: ChunkStrategyName): string {
+		const config = this.config;
+		const settings = JSON.stringify([
+			config.embedWithHeaderPath,
+			config.embedCalloutTitles,
+			config.stripMarkdown,
+			config.plainTextExtensions,
+			config.indexHeadings,
+			config.multiVector,
+			config.maxChunksPerDoc,
+			config.chunkOverflowPolicy,
+			strategy ?? config.chunkStrategy,
+			this.chunker.getOptions(),
+		]);
+		return hashContent(`${settings}\n${normalizeNewlines(content)}`);
+	}
+
+	/**
+	 * Whether the backend already holds this exact content, indexed with the current settings
+	 */
+	private async isUnchanged(
+		filePath: string,
+		content: string,
+		strategy?: ChunkStrategyName,
+	): Promise<boolean> {
+		const info = await this.backend.getPathInfo(normalizeVaultPath(filePath));
+		return (
+			!!info?.contentHash &&
+			info.contentHash === this.fileHash(content, strategy)
+		);
+	}
+
+	/**
+	 * Drop or stub out the unbreakable chunks the chunker kept whole
+	 * "skip" removes them, "placeholder" stores "[omitted binary blob N chars]" in their place
+	 */
+	private applyOversizedPolicy(chunks: ChunkResult[]): {
+		chunks: ChunkResult[];
+		oversized: number;
+	} {
+		const oversized = chunks.filter((chunk) => chunk.oversized).length;
+		if (oversized === 0) {
+			return { chunks, oversized };
+		}
+
+		if (this.config.oversizedPolicy === "skip") {
+			return {
+				chunks: chunks.filter((chunk) => !chunk.oversized),
+				oversized,
+			};
+		}
+		return {
+			chunks: chunks.map((chunk) =>
+				chunk.oversized
+					? {
+							...chunk,
+							content: `[omitted binary blob ${Array.from(chunk.content.trim()).length} chars]`,
+						}
+					: chunk,
+			),
+			oversized,
+		};
+	}
+
+	/**
+	 * Clamp a runaway document to `maxChunksPerDoc` chunks
+	 * "merge" folds the tail into the last allowed chunk, "truncate" drops it
+	 */
+	private capChunks(chunks: ChunkResult[]): {
+		chunks: ChunkResult[];
+		capped: boolean;
+	} {
+		const max = this.config.maxChunksPerDoc;
+		if (!max || chunks.length <= max) {
+			return { chunks, capped: false };
+		}
+
+		if (this.config.chunkOverflowPolicy === "truncate") {
+			return { chunks: chunks.slice(0, max), capped: true };
+		}
+
+		const tail = chunks.slice(max - 1);
+		const last = tail[tail.length - 1];
+		const merged: ChunkResult = {
+			...tail[0],
+			content: tail.map((chunk) => chunk.content).join(""),
+			endPos: last.endPos,
+			end_line: last.end_line,
+		};
+		return { chunks: [...chunks.slice(0, max - 1), merged], capped: true };
+	}
+
+	/**
+	 * Whether a file type can be indexed with the current configuration
+	 */
+	isIndexable(filePath: string): boolean {
+		return (
+			getFileKind(filePath, this.config.plainTextExtensions) !==
+			"unsupported"
+		);
+	}
+
+	/**
+	 * Re-index several files, continuing past failures
+	 * Embedding calls share the EmbeddingService concurrency limit
+	 */
+	async indexFiles(files: IndexFileInput[]): Promise<BatchIndexReport> {
+		const report = createBatchReport();
+		for (const file of files) {
+			addToBatchReport(
+				report,
+				await this.reindexFile(
+					file.filePath,
+					async () => file.content,
+					file.strategy,
+				),
+			);
+		}
+		return report;
+	}
+
+	/**
+	 * Re-index many files with a bounded worker pool, reading each file only when a worker picks it up
+	 * Up to `indexConcurrency` files are in flight; each file's chunks are flushed once it is indexed,
+	 * so memory stays flat on large vaults
+	 * @param read - Loads a file's content, failures are reported per file
+	 * @param onProgress - Called after each file, successful or not
+	 */
+	async indexPaths(
+		filePaths: string[],
+		read: (filePath: string) => Promise<string>,
+		onProgress?: (progress: BatchIndexProgress) => void,
+	): Promise<BatchIndexReport> {
+		const report = createBatchReport();
+		let next = 0;
+
+		const worker = async (): Promise<void> => {
+			while (next < filePaths.length) {
+				const filePath = filePaths[next++];
+				addToBatchReport(
+					report,
+					await this.reindexFile(filePath, () => read(filePath)),
+				);
+
+				onProgress?.({
+					done: report.files.length,
+					total: filePaths.length,
+					failed: report.failed,
+				});
+			}
+		};
+
+		const workers = Math.max(
+			1,
+			Math.min(this.config.indexConcurrency, filePaths.length),
+		);
+		await Promise.all(Array.from({ length: workers }, () => worker()));
+
+		return report;
+	}
+
+	/**
+	 * Read and re-index one file, capturing failures in the report entry
+	 * With `skipUnchangedFiles`, a file whose hash matches the stored one is reported unchanged, not re-embedded
+	 */
+	private async reindexFile(
+		filePath: string,
+		read: () => Promise<string>,
+		strategy?: ChunkStrategyName,
+	): Promise<IndexFileReport> {
+		try {
+			const content = await read();
+			if (
+				this.config.skipUnchangedFiles &&
+				(await this.isUnchanged(filePath, content, strategy))
+			) {
+				return { filePath, success: true, chunks: 0, unchanged: true };
+			}
+
+			let capped = false;
+			let skipped = 0;
+			let oversized = 0;
+			const chunks = await this.updateFile(
+				filePath,
+				content,
+				(progress) => {
+					capped = progress.capped;
+					skipped = progress.skipped;
+					oversized = progress.oversized;
+				},
+				strategy,
+			);
+			return {
+				filePath,
+				success: true,
+				chunks,
+				...(capped && { capped }),
+				...(skipped > 0 && { skipped }),
+				...(oversized > 0 && { oversized }),
+			};
+		} catch (error) {
+			console.error("[MemoEcho] Batch index failed for", filePath, error);
+			return {
+				filePath,
+				success: false,
+				chunks: 0,
+				error: getErrorMessage(error),
+			};
+		}
 	}
 
 	/**
@@ -119,6 +587,7 @@ export class VectorIndexManager {
 		);
 
 		const concepts: ExtractedConceptDetail[] = [];
+		const note: NoteInfo = { title: extractTitle(content, filePath) };
 
 		for (const chunk of chunks) {
 			try {
@@ -147,6 +616,7 @@ export class VectorIndexManager {
 					filePath,
 					chunk,
 					extractedMetadata,
+					note,
 				);
 			} catch (error) {
 				console.error(
@@ -175,11 +645,51 @@ export class VectorIndexManager {
 	private async indexChunk(
 		filePath: string,
 		chunk: ChunkResult,
+		note: NoteInfo,
+		signal?: IndexAbortSignal,
 	): Promise<void> {
 		const extractedMetadata = await this.metadataExtractor.extract(
 			chunk.content,
 		);
-		await this.indexChunkWithMetadata(filePath, chunk, extractedMetadata);
+		await this.indexChunkWithMetadata(
+			filePath,
+			chunk,
+			extractedMetadata,
+			note,
+			signal,
+		);
+	}
+
+	/**
+	 * Index a section's heading text as its own point, so a query for the title matches it directly
+	 * Called once per section; skipped for tiny chunks the heading already dominates
+	 * (only checked when the heading line is part of the chunk content)
+	 */
+	private async indexHeading(
+		filePath: string,
+		chunk: ChunkResult,
+		note: NoteInfo,
+		signal?: IndexAbortSignal,
+	): Promise<void> {
+		const heading = chunk.headers[chunk.headers.length - 1];
+		if (!heading) {
+			return;
+		}
+
+		const content = chunk.content.trim();
+		const headingLine = content.split("\n")[0];
+		if (content.startsWith("#") && headingLine.length * 2 >= content.length) {
+			return;
+		}
+
+		await this.indexChunkWithMetadata(
+			filePath,
+			{ ...chunk, content: heading.text, end_line: chunk.start_line },
+			{ summary: "", tags: [], category: "", concepts: [] },
+			note,
+			signal,
+			"heading",
+		);
 	}
 
 	private async indexChunkWithMetadata(
@@ -191,18 +701,28 @@ export class VectorIndexManager {
 			category: string;
 			concepts: ExtractedMetadataConcept[];
 		},
+		note: NoteInfo,
+		signal?: IndexAbortSignal,
+		kind: "chunk" | "heading" = "chunk",
 	): Promise<void> {
-		const chunkId = `${filePath}-chunk-${chunk.index}`;
-
-		// Generate three embeddings in parallel
-		const [contentEmbedding, summaryEmbedding, titleEmbedding] =
-			await Promise.all([
-				this.embeddingService.embed(chunk.content),
-				this.embeddingService.embed(
-					extractedMetadata.summary || chunk.content.slice(0, 200),
-				),
-				this.embeddingService.embed(chunk.header_path || filePath),
-			]);
+		filePath = normalizeVaultPath(filePath);
+		const chunkId = `${filePath}-${kind}-${chunk.index}`;
+
+		const vectors = await this.embedChunkVectors(
+			chunk,
+			extractedMetadata.summary,
+			filePath,
+		);
+		const contentEmbedding = vectors[VECTOR_NAMES.CONTENT];
+		const subVectors =
+			kind === "chunk"
+				? await this.embedSubVectors(chunk, contentEmbedding)
+				: undefined;
+
+		// The run timed out while embedding; don't cache or queue this chunk
+		if (signal?.aborted) {
+			return;
+		}
 
 		const conceptNames = extractedMetadata.concepts
 			.map((concept) => concept.name)
@@ -211,7 +731,13 @@ export class VectorIndexManager {
 		// Simplified payload (v0.4.0)
 		const payload = {
 			filePath,
+			filePathKey: toPathKey(filePath),
+			folders: getAncestorFolders(filePath),
+			title: note.title, // Display title, separate from the filePath key
+			...(note.contentHash && { contentHash: note.contentHash }),
+			...(note.indexRunId && { indexRunId: note.indexRunId }),
 			header_path: chunk.header_path,
+			depth: chunk.headers.length, // Headings in header_path, 0 before the first heading
 			start_line: chunk.start_line,
 			end_line: chunk.end_line,
 			content: chunk.content,
@@ -222,6 +748,7 @@ export class VectorIndexManager {
 			].filter(Boolean),
 			concepts: conceptNames,
 			type: "chunk",
+			...(kind === "heading" && { heading_only: true }),
 			word_count: chunk.content.length,
 			indexedAt: Date.now(),
 		};
@@ -241,17 +768,87 @@ export class VectorIndexManager {
 		// Add to multi-vector persist queue
 		const queuedChunk: MultiVectorQueuedChunk = {
 			id: chunkId,
-			vectors: {
-				[VECTOR_NAMES.CONTENT]: contentEmbedding,
-				[VECTOR_NAMES.SUMMARY]: summaryEmbedding,
-				[VECTOR_NAMES.TITLE]: titleEmbedding,
-			},
+			vectors,
+			...(subVectors && { subVectors }),
 			metadata: payload,
 		};
 
 		this.persistQueue.enqueueMultiVector(queuedChunk);
 	}
 
+	/**
+	 * Embed a chunk's content, summary and title vectors in parallel
+	 */
+	private async embedChunkVectors(
+		chunk: Pick<ChunkResult, "content" | "header_path">,
+		summary: string,
+		filePath: string,
+	): Promise<Record<VECTOR_NAMES, number[]>> {
+		const [content, summaryVector, title] = await Promise.all([
+			this.embeddingService.embed(this.buildEmbedInput(chunk)),
+			this.embeddingService.embed(
+				summary || truncateChars(chunk.content, 200),
+			),
+			this.embeddingService.embed(chunk.header_path || filePath),
+		]);
+		return {
+			[VECTOR_NAMES.CONTENT]: content,
+			[VECTOR_NAMES.SUMMARY]: summaryVector,
+			[VECTOR_NAMES.TITLE]: title,
+		};
+	}
+
+	/**
+	 * Chunk content as preprocessed for the embedder, before any prefix
+	 */
+	private embedContent(chunk: Pick<ChunkResult, "content">): string {
+		return this.config.stripMarkdown
+			? stripMarkdown(chunk.content)
+			: chunk.content;
+	}
+
+	/**
+	 * With `multiVector`, one vector per paragraph (up to MAX_SUB_VECTORS) for the
+	 * `content_multi_vec` multivector; a single paragraph reuses the content vector
+	 */
+	private async embedSubVectors(
+		chunk: Pick<ChunkResult, "content">,
+		contentEmbedding: number[],
+	): Promise<number[][] | undefined> {
+		if (!this.config.multiVector) {
+			return undefined;
+		}
+
+		const paragraphs = chunk.content
+			.split(/\n[ \t]*\n/)
+			.map((paragraph) => this.embedContent({ content: paragraph }).trim())
+			.filter(Boolean)
+			.slice(0, MAX_SUB_VECTORS);
+		if (paragraphs.length < 2) {
+			return [contentEmbedding];
+		}
+		return Promise.all(
+			paragraphs.map((paragraph) => this.embeddingService.embed(paragraph)),
+		);
+	}
+
+	/**
+	 * Text sent to the embedder for a chunk; the stored content stays unchanged
+	 */
+	private buildEmbedInput(
+		chunk: Pick<ChunkResult, "content" | "header_path">,
+	): string {
+		const content = this.embedContent(chunk);
+		const prefix: string[] = [];
+		if (this.config.embedWithHeaderPath && chunk.header_path) {
+			prefix.push(chunk.header_path);
+		}
+		if (this.config.embedCalloutTitles) {
+			prefix.push(...getCalloutLabels(chunk.content));
+		}
+		return prefix.length > 0 ? `${prefix.join("\n")}\n${content}` : content;
+	}
+
 	private buildChunksFromSemantic(
 		content: string,
 		semanticChunks: SemanticChunk[],
@@ -388,12 +985,12 @@ export class VectorIndexManager {
 		// Calculate cosine similarity for each cached chunk
 		const results: SearchResult[] = allCached.map((chunk) => ({
 			id: chunk.id,
-			score: this.cosineSimilarity(queryEmbedding, chunk.embedding),
+			score: cosineSimilarity(queryEmbedding, chunk.embedding),
 			metadata: chunk.metadata,
 		}));
 
 		// Sort by score and limit
-		return results.sort((a, b) => b.score - a.score).slice(0, limit);
+		return results.sort(compareSearchResults).slice(0, limit);
 	}
 
 	/**
@@ -420,30 +1017,10 @@ export class VectorIndexManager {
 
 		// Sort by score and limit
 		return Array.from(resultMap.values())
-			.sort((a, b) => b.score - a.score)
+			.sort(compareSearchResults)
 			.slice(0, limit);
 	}
 
-	/**
-	 * Calculate cosine similarity
-	 */
-	private cosineSimilarity(a: number[], b: number[]): number {
-		if (a.length !== b.length) return 0;
-
-		let dotProduct = 0;
-		let normA = 0;
-		let normB = 0;
-
-		for (let i = 0; i < a.length; i++) {
-			dotProduct += a[i] * b[i];
-			normA += a[i] * a[i];
-			normB += b[i] * b[i];
-		}
-
-		const denominator = Math.sqrt(normA) * Math.sqrt(normB);
-		return denominator === 0 ? 0 : dotProduct / denominator;
-	}
-
 	/**
 	 * Flush persist queue to vector store (v0.4.0: uses multi-vector)
 	 */
@@ -456,7 +1033,9 @@ export class VectorIndexManager {
 	 */
 	async onFileSave(filePath: string): Promise<void> {
 		// Flush chunks for this file
-		const fileChunks = this.persistQueue.getByFilePath(filePath);
+		const fileChunks = this.persistQueue.getByFilePath(
+			normalizeVaultPath(filePath),
+		);
 
 		if (fileChunks.length > 0) {
 			await this.persistQueue.flushMultiVector();
@@ -467,16 +1046,77 @@ export class VectorIndexManager {
 	 * Remove file from cache and queue
 	 */
 	removeFile(filePath: string): void {
+		filePath = normalizeVaultPath(filePath);
 		this.memoryCache.deleteByFilePath(filePath);
 		this.persistQueue.removeByFilePath(filePath);
 	}
 
 	/**
-	 * Update file (remove old, index new)
+	 * Regenerate every stored chunk's vectors from its stored content with the current
+	 * embedding model, e.g. after switching to a model of the same dimension
+	 * With `multiVector` the paragraph sub-vectors are regenerated too, otherwise dropped
+	 * Pending chunks are flushed first; cached embeddings are dropped since they came from the old model
+	 */
+	async reembedAll(
+		onProgress?: (progress: ReembedReport) => void,
+	): Promise<ReembedReport> {
+		await this.persistQueue.flushMultiVector();
+
+		const { dimension } = await this.backend.getHealth();
+		const probe = await this.embeddingService.embed("dimension probe");
+		if (dimension !== null && probe.length !== dimension) {
+			throw new Error(
+				`当前模型向量维度为 ${probe.length}，索引为 ${dimension} 维，请清空后重新索引`,
+			);
+		}
+
+		this.memoryCache.clear();
+		return this.backend.reembedPoints(async (metadata) => {
+			const chunk = {
+				content: metadata.content ?? "",
+				header_path: metadata.header_path ?? "",
+			};
+			const vectors = await this.embedChunkVectors(
+				chunk,
+				metadata.summary ?? "",
+				metadata.filePath ?? "",
+			);
+			const subVectors = metadata.heading_only
+				? undefined
+				: await this.embedSubVectors(chunk, vectors[VECTOR_NAMES.CONTENT]);
+			return { vectors, ...(subVectors && { subVectors }) };
+		}, onProgress);
+	}
+
+	/**
+	 * Move a file's chunks to a new path without re-embedding
+	 * Pending chunks are flushed first so the backend holds every point to rename
 	 */
-	async updateFile(filePath: string, content: string): Promise<void> {
+	async renameFile(oldPath: string, newPath: string): Promise<number> {
+		oldPath = normalizeVaultPath(oldPath);
+		newPath = normalizeVaultPath(newPath);
+
+		await this.persistQueue.flushMultiVector();
+		for (const chunk of this.memoryCache.getByFilePath(oldPath)) {
+			chunk.metadata.filePath = newPath;
+			chunk.metadata.filePathKey = toPathKey(newPath);
+			chunk.metadata.folders = getAncestorFolders(newPath);
+		}
+
+		return this.backend.renameFilePath(oldPath, newPath);
+	}
+
+	/**
+	 * Update file (drop cached and queued chunks, then re-index, which replaces the stored points)
+	 */
+	async updateFile(
+		filePath: string,
+		content: string,
+		onProgress?: (progress: IndexProgress) => void,
+		strategy?: ChunkStrategyName,
+	): Promise<number> {
 		this.removeFile(filePath);
-		await this.indexFile(filePath, content);
+		return this.indexFile(filePath, content, onProgress, strategy);
 	}
 
 	/**
@@ -525,3 +1165,23 @@ export class VectorIndexManager {
 		this.persistQueue.stop();
 	}
 }
+
+function createBatchReport(): BatchIndexReport {
+	return { files: [], indexed: 0, unchanged: 0, failed: 0, totalChunks: 0 };
+}
+
+function addToBatchReport(
+	report: BatchIndexReport,
+	file: IndexFileReport,
+): void {
+	report.files.push(file);
+	if (file.success) {
+		report.indexed++;
+		report.totalChunks += file.chunks;
+		if (file.unchanged) {
+			report.unchanged++;
+		}
+	} else {
+		report.failed++;
+	}
+}
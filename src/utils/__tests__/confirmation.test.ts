@@ -0,0 +1,20 @@
+/**
+ * Confirmation Unit Tests
+ */
+
+import { describe, it, expect } from 'vitest';
+import { confirmsCollection } from '../confirmation';
+
+describe('confirmsCollection', () => {
+    it('should accept the exact collection name', () => {
+        expect(confirmsCollection('obsidian_notes', 'obsidian_notes')).toBe(true);
+        expect(confirmsCollection('  obsidian_notes ', 'obsidian_notes')).toBe(true);
+    });
+
+    it('should reject a missing or different name', () => {
+        expect(confirmsCollection('', 'obsidian_notes')).toBe(false);
+        expect(confirmsCollection('Obsidian_Notes', 'obsidian_notes')).toBe(false);
+        expect(confirmsCollection('other', 'obsidian_notes')).toBe(false);
+        expect(confirmsCollection('', '')).toBe(false);
+    });
+});
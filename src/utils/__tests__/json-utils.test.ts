@@ -0,0 +1,24 @@
+/**
+ * JSON Utils Unit Tests
+ */
+
+import { describe, it, expect } from 'vitest';
+import { parseModelJson } from '../json-utils';
+
+describe('parseModelJson', () => {
+    it('should parse fenced JSON with surrounding prose', () => {
+        const text = 'Here you go:\n```json\n{"summary": "ok", "tags": ["a"]}\n```';
+
+        expect(parseModelJson(text, 'OpenAI')).toEqual({ summary: 'ok', tags: ['a'] });
+    });
+
+    it('should throw a descriptive error for malformed JSON', () => {
+        expect(() => parseModelJson('{"summary": "ok", "tags": [}', 'Ollama'))
+            .toThrow(/^Ollama 返回的 JSON 无效: .+ \(原文: "\{"summary": "ok", "tags": \[\}"\)$/);
+    });
+
+    it('should reject non-object JSON', () => {
+        expect(() => parseModelJson('[1, 2]', 'Ollama')).toThrow('Ollama 返回的 JSON 应为对象');
+        expect(() => parseModelJson(undefined, 'Ollama')).toThrow('JSON 无效');
+    });
+});
@@ -0,0 +1,43 @@
+/**
+ * Path Utils Unit Tests
+ */
+
+import { describe, it, expect } from 'vitest';
+import { getAncestorFolders, normalizeVaultPath, toPathKey } from '../path-utils';
+
+describe('normalizeVaultPath', () => {
+    it('should keep spaces literal', () => {
+        expect(normalizeVaultPath('My Vacation/Eiffel Tower.md')).toBe('My Vacation/Eiffel Tower.md');
+    });
+
+    it('should decode percent-encoding exactly once', () => {
+        expect(normalizeVaultPath('My%20Vacation/Eiffel%20Tower.md')).toBe('My Vacation/Eiffel Tower.md');
+        expect(normalizeVaultPath('notes/50%2520off.md')).toBe('notes/50%20off.md');
+    });
+
+    it('should leave malformed sequences untouched', () => {
+        expect(normalizeVaultPath('notes/100%.md')).toBe('notes/100%.md');
+    });
+
+    it('should map encoded and literal forms to the same key', () => {
+        const stored = normalizeVaultPath('./My Vacation\\Eiffel Tower.md');
+        expect(normalizeVaultPath('My%20Vacation/Eiffel%20Tower.md')).toBe(stored);
+    });
+});
+
+describe('toPathKey', () => {
+    it('should match paths differing in case and separators', () => {
+        expect(toPathKey('Notes\\A.md')).toBe(toPathKey('notes/a.md'));
+        expect(toPathKey('My%20Notes/A.md')).toBe('my notes/a.md');
+    });
+});
+
+describe('getAncestorFolders', () => {
+    it('should list every ancestor folder, outermost first', () => {
+        expect(getAncestorFolders('a/b/c.md')).toEqual(['a', 'a/b']);
+    });
+
+    it('should return no folders for a root-level note', () => {
+        expect(getAncestorFolders('note.md')).toEqual([]);
+    });
+});
@@ -0,0 +1,91 @@
+/**
+ * Text Utils Unit Tests
+ */
+
+import { describe, it, expect } from 'vitest';
+import { buildPreview, buildSnippet, extractTitle, truncateChars, truncateUtf8Bytes } from '../text-utils';
+
+describe('buildPreview', () => {
+    it('should return content unchanged when it fits', () => {
+        expect(buildPreview('  Short text.  ', 50)).toBe('Short text.');
+    });
+
+    it('should start at the next sentence and end with an ellipsis when trimmed', () => {
+        const content = 'and then it ended. The next sentence is here. Another one follows after that with more words.';
+        const preview = buildPreview(content, 60);
+
+        expect(preview.startsWith('The next sentence')).toBe(true);
+        expect(preview.endsWith('…')).toBe(true);
+        expect(Array.from(preview).length).toBeLessThanOrEqual(61);
+    });
+
+    it('should cut CJK text on a sentence boundary without splitting characters', () => {
+        const content = '第一句话很长很长很长。第二句话也不短。第三句话。';
+
+        expect(buildPreview(content, 14)).toBe('第一句话很长很长很长。…');
+    });
+});
+
+describe('buildSnippet', () => {
+    const content = 'Qdrant stores the vectors on disk. Ollama computes embeddings for every chunk locally. '
+        + 'The settings tab lists both services.';
+
+    it('should center the window on the sentence matching the query', () => {
+        const snippet = buildSnippet(content, 'how does ollama embed', 60, ['how', 'does']);
+
+        expect(snippet).toContain('Ollama computes embeddings for every chunk locally.');
+        expect(Array.from(snippet).length).toBeLessThanOrEqual(60);
+    });
+
+    it('should cut a sentence longer than the window and fall back to the first sentence', () => {
+        expect(buildSnippet(content, 'ollama', 10)).toBe('Ollama co');
+        expect(buildSnippet(content, 'unrelated', 20)).toBe('Qdrant stores the ve');
+    });
+});
+
+describe('extractTitle', () => {
+    const content = '---\ntitle: "Frontmatter Title"\ntags: [a]\n---\n# Heading Title\nBody\n';
+
+    it('should prefer the frontmatter title', () => {
+        expect(extractTitle(content, 'notes/file-name.md')).toBe('Frontmatter Title');
+    });
+
+    it('should fall back to the first H1', () => {
+        const withoutTitle = content.replace('title: "Frontmatter Title"\n', '');
+        expect(extractTitle(withoutTitle, 'notes/file-name.md')).toBe('Heading Title');
+    });
+
+    it('should fall back to the file name without extension', () => {
+        expect(extractTitle('## Only a subheading\nBody\n', 'notes/file-name.md')).toBe('file-name');
+    });
+});
+
+describe('truncateChars', () => {
+    it('should count code points and never split a surrogate pair', () => {
+        expect(truncateChars('a😀b😀c', 2)).toBe('a😀');
+        expect(truncateChars('😀😀😀', 1)).toBe('😀');
+    });
+
+    it('should return the input unchanged when it fits exactly', () => {
+        expect(truncateChars('你好世界', 4)).toBe('你好世界');
+        expect(truncateChars('', 3)).toBe('');
+    });
+
+    it('should cut over-budget input', () => {
+        expect(truncateChars('你好世界', 2)).toBe('你好');
+        expect(truncateChars('abc', 0)).toBe('');
+    });
+});
+
+describe('truncateUtf8Bytes', () => {
+    it('should snap back to a whole multi-byte character', () => {
+        expect(truncateUtf8Bytes('你好', 4)).toBe('你');
+        expect(truncateUtf8Bytes('a😀', 4)).toBe('a');
+        expect(truncateUtf8Bytes('é', 1)).toBe('');
+    });
+
+    it('should keep input that fits exactly', () => {
+        expect(truncateUtf8Bytes('你好', 6)).toBe('你好');
+        expect(truncateUtf8Bytes('a😀', 5)).toBe('a😀');
+    });
+});
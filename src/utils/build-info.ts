@@ -0,0 +1,51 @@
+/**
+ * Build and runtime target information for support requests
+ * __GIT_SHA__ and __BUILT_AT__ are injected by esbuild; unbundled runs (tests) fall back to "unknown"
+ */
+
+declare const __GIT_SHA__: string | undefined;
+declare const __BUILT_AT__: string | undefined;
+
+const UNKNOWN = 'unknown';
+
+export interface BuildInfo {
+    version: string;
+    gitSha: string;
+    builtAt: string;
+    qdrantUrl: string;
+    embeddingUrl: string;
+    embeddingModel: string;
+}
+
+export function getBuildInfo(
+    version: string,
+    targets: { qdrantUrl: string; embeddingUrl: string; embeddingModel: string }
+): BuildInfo {
+    return {
+        version: version || UNKNOWN,
+        gitSha: (typeof __GIT_SHA__ !== 'undefined' && __GIT_SHA__) || UNKNOWN,
+        builtAt: (typeof __BUILT_AT__ !== 'undefined' && __BUILT_AT__) || UNKNOWN,
+        qdrantUrl: stripUrlSecrets(targets.qdrantUrl),
+        embeddingUrl: stripUrlSecrets(targets.embeddingUrl),
+        embeddingModel: targets.embeddingModel || UNKNOWN,
+    };
+}
+
+/**
+ * URL without credentials, query or fragment (where API keys tend to end up)
+ * e.g. "https://user:pw@host:6333/?api-key=x" => "https://host:6333"
+ */
+export function stripUrlSecrets(url: string): string {
+    if (!url) {
+        return UNKNOWN;
+    }
+    try {
+        const parsed = new URL(url);
+        if (parsed.origin !== 'null') {
+            return `${parsed.origin}${parsed.pathname}`.replace(/\/+$/, '');
+        }
+    } catch {
+        // Not an absolute URL, only the query can be dropped
+    }
+    return url.replace(/[?#].*$/, '');
+}
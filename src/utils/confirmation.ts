@@ -0,0 +1,8 @@
+/**
+ * Whether a typed confirmation names the collection a destructive action targets
+ * Surrounding whitespace is ignored; matching is case-sensitive, like Qdrant collection names
+ */
+export function confirmsCollection(input: string, collectionName: string): boolean {
+    const expected = collectionName.trim();
+    return expected.length > 0 && input.trim() === expected;
+}
@@ -0,0 +1,32 @@
+import { getErrorMessage } from './error';
+import { truncateChars } from './text-utils';
+
+// Characters of the raw response quoted in parse errors
+const SNIPPET_CHARS = 80;
+
+/**
+ * Parse a JSON object returned by an LLM
+ * Strips Markdown code fences and surrounding prose; on failure throws an error naming
+ * the source, the parser's complaint and the start of the raw text
+ */
+export function parseModelJson(text: string | undefined, source: string): Record<string, any> {
+    const raw = (text ?? '').trim();
+    const unfenced = raw.replace(/```(?:json)?\n?|\n?```/g, '').trim();
+    const start = unfenced.indexOf('{');
+    const end = unfenced.lastIndexOf('}');
+    const candidate = start !== -1 && end > start ? unfenced.slice(start, end + 1) : unfenced;
+
+    let result: unknown;
+    try {
+        result = JSON.parse(candidate);
+    } catch (error) {
+        throw new Error(
+            `${source} 返回的 JSON 无效: ${getErrorMessage(error)} (原文: "${truncateChars(raw, SNIPPET_CHARS)}")`
+        );
+    }
+
+    if (typeof result !== 'object' || result === null || Array.isArray(result)) {
+        throw new Error(`${source} 返回的 JSON 应为对象 (原文: "${truncateChars(raw, SNIPPET_CHARS)}")`);
+    }
+    return result as Record<string, any>;
+}
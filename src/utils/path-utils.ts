@@ -0,0 +1,55 @@
+export type FileKind = 'markdown' | 'plaintext' | 'unsupported';
+
+/**
+ * Detect how a file should be chunked from its extension
+ */
+export function getFileKind(path: string, plainTextExtensions: string[]): FileKind {
+    const dot = path.lastIndexOf('.');
+    const extension = dot === -1 ? '' : path.slice(dot + 1).toLowerCase();
+
+    if (extension === 'md') {
+        return 'markdown';
+    }
+    return plainTextExtensions.includes(extension) ? 'plaintext' : 'unsupported';
+}
+
+/**
+ * Normalize a vault path so index-time and delete/filter-time paths match
+ * Decodes percent-encoding once, keeps spaces literal, uses forward slashes
+ */
+export function normalizeVaultPath(path: string): string {
+    let normalized = path.trim();
+
+    if (/%[0-9A-Fa-f]{2}/.test(normalized)) {
+        try {
+            normalized = decodeURIComponent(normalized);
+        } catch {
+            // Malformed sequence (e.g. a literal "%" in a file name), keep as-is
+        }
+    }
+
+    return normalized.replace(/\\/g, '/').replace(/^\.?\//, '');
+}
+
+/**
+ * Case-folded path, stored as `filePathKey` for case-insensitive matching
+ */
+export function toPathKey(path: string): string {
+    return normalizeVaultPath(path).toLowerCase();
+}
+
+/**
+ * Every ancestor folder of a vault path, outermost first: "a/b/c.md" => ["a", "a/b"]
+ * Stored as the `folders` payload so a folder filter also matches its subfolders
+ */
+export function getAncestorFolders(path: string): string[] {
+    const segments = normalizeVaultPath(path).split('/').slice(0, -1);
+    return segments.map((_, i) => segments.slice(0, i + 1).join('/'));
+}
+
+/**
+ * Normalize a folder filter value: vault path rules, no trailing slash
+ */
+export function normalizeFolder(folder: string): string {
+    return normalizeVaultPath(folder).replace(/\/+$/, '');
+}
@@ -0,0 +1,43 @@
+/**
+ * Semaphore - Limits the number of concurrently running async tasks
+ */
+export class Semaphore {
+    private active = 0;
+    private waiters: Array<() => void> = [];
+
+    constructor(private readonly permits: number) {
+        if (permits < 1) {
+            throw new Error(`Semaphore permits must be >= 1, got ${permits}`);
+        }
+    }
+
+    async acquire(): Promise<void> {
+        if (this.active < this.permits) {
+            this.active++;
+            return;
+        }
+        await new Promise<void>((resolve) => this.waiters.push(resolve));
+    }
+
+    release(): void {
+        const next = this.waiters.shift();
+        if (next) {
+            // Hand the permit directly to the next waiter
+            next();
+        } else {
+            this.active--;
+        }
+    }
+
+    /**
+     * Run a task once a permit is available
+     */
+    async run<T>(task: () => Promise<T>): Promise<T> {
+        await this.acquire();
+        try {
+            return await task();
+        } finally {
+            this.release();
+        }
+    }
+}
@@ -0,0 +1,226 @@
+/**
+ * Normalize line endings: collapse `\r\n` and lone `\r` to `\n`
+ */
+export function normalizeNewlines(content: string): string {
+    return content.replace(/\r\n?/g, '\n');
+}
+
+/**
+ * Cut to at most `maxChars` code points, never splitting a surrogate pair
+ */
+export function truncateChars(text: string, maxChars: number): string {
+    if (text.length <= maxChars) {
+        return text;
+    }
+
+    let end = 0;
+    for (let count = 0; count < maxChars && end < text.length; count++) {
+        end += text.codePointAt(end)! > 0xffff ? 2 : 1;
+    }
+    return text.slice(0, end);
+}
+
+/**
+ * Cut to at most `maxBytes` of UTF-8, snapping back to the last whole code point
+ */
+export function truncateUtf8Bytes(text: string, maxBytes: number): string {
+    let bytes = 0;
+    let end = 0;
+    while (end < text.length) {
+        const code = text.codePointAt(end)!;
+        const size = code < 0x80 ? 1 : code < 0x800 ? 2 : code < 0x10000 ? 3 : 4;
+        if (bytes + size > maxBytes) {
+            break;
+        }
+        bytes += size;
+        end += code > 0xffff ? 2 : 1;
+    }
+    return text.slice(0, end);
+}
+
+/**
+ * Words of a query that are not stop words; each CJK character counts as one token
+ */
+export function significantTokens(query: string, stopWords: string[]): string[] {
+    const stop = new Set(stopWords.map(word => word.toLowerCase()));
+    // One kana/CJK character, or a run of anything but whitespace, CJK, full-width and ASCII punctuation
+    const tokens = query.toLowerCase().match(/[\u3040-\u30ff\u3400-\u9fff\uf900-\ufaff]|[^\s\u3000-\u303f\u3040-\u30ff\u3400-\u9fff\uf900-\ufaff\uff00-\uffef!-\/:-@[-`{-~]+/g) || [];
+    return tokens.filter(token => !stop.has(token));
+}
+
+const SENTENCE_END = /[.!?。！？…]/;
+
+/**
+ * Cut to at most `maxChars` code points, preferring to end after a sentence or at a
+ * word break in the second half of the budget
+ */
+export function truncateAtBoundary(text: string, maxChars: number): string {
+    const cut = truncateChars(text, maxChars);
+    if (cut.length === text.length) {
+        return text;
+    }
+
+    const chars = Array.from(cut);
+    const half = Math.floor(chars.length / 2);
+    for (let i = chars.length - 1; i >= half; i--) {
+        if (SENTENCE_END.test(chars[i]) || chars[i] === '\n') {
+            return chars.slice(0, i + 1).join('');
+        }
+    }
+    for (let i = chars.length - 1; i >= half; i--) {
+        if (/\s/.test(chars[i])) {
+            return chars.slice(0, i).join('');
+        }
+    }
+    return cut;
+}
+
+/**
+ * Build a short preview that starts and ends on sentence boundaries where possible
+ * Lengths are counted in code points, so CJK and emoji are never split
+ */
+export function buildPreview(content: string, maxChars: number): string {
+    let chars = Array.from(content.trim());
+
+    if (chars.length <= maxChars) {
+        return chars.join('');
+    }
+
+    // Chunk starts mid-sentence: skip to the next sentence start if one is close
+    if (/^[a-z]/.test(chars[0])) {
+        const end = findSentenceEnd(chars, 0, Math.floor(maxChars / 2));
+        if (end !== -1) {
+            chars = Array.from(chars.slice(end + 1).join('').trimStart());
+        }
+    }
+
+    if (chars.length <= maxChars) {
+        return chars.join('');
+    }
+
+    const window = chars.slice(0, maxChars);
+    let cut = -1;
+    for (let i = window.length - 1; i >= Math.floor(maxChars / 2); i--) {
+        if (SENTENCE_END.test(window[i])) {
+            cut = i + 1;
+            break;
+        }
+    }
+    if (cut === -1) {
+        const lastSpace = window.lastIndexOf(' ');
+        cut = lastSpace > maxChars / 2 ? lastSpace : window.length;
+    }
+
+    return window.slice(0, cut).join('').trimEnd() + '…';
+}
+
+/**
+ * The sentence sharing the most significant tokens with the query, widened with
+ * surrounding context to at most `maxChars` code points; the first sentence when none match
+ */
+export function buildSnippet(content: string, query: string, maxChars: number, stopWords: string[] = []): string {
+    const chars = Array.from(content);
+    const tokens = significantTokens(query, stopWords);
+
+    let best = { start: 0, end: chars.length, score: -1 };
+    let start = 0;
+    for (let i = 0; i <= chars.length; i++) {
+        if (i < chars.length && !SENTENCE_END.test(chars[i]) && chars[i] !== '\n') {
+            continue;
+        }
+        const end = Math.min(i + 1, chars.length);
+        const sentence = chars.slice(start, end).join('').toLowerCase();
+        if (sentence.trim()) {
+            const score = tokens.filter(token => sentence.includes(token)).length;
+            if (score > best.score) {
+                best = { start, end, score };
+            }
+        }
+        start = end;
+    }
+
+    // Center the sentence in the window, shifting it back inside the content at either end
+    const from = Math.max(0, Math.min(
+        best.start - Math.floor(Math.max(0, maxChars - (best.end - best.start)) / 2),
+        chars.length - maxChars
+    ));
+    return chars.slice(from, from + maxChars).join('').trim();
+}
+
+function findSentenceEnd(chars: string[], from: number, to: number): number {
+    for (let i = from; i < Math.min(to, chars.length); i++) {
+        if (SENTENCE_END.test(chars[i])) {
+            return i;
+        }
+    }
+    return -1;
+}
+
+/**
+ * Remove Markdown syntax noise before embedding
+ * Drops HTML comments and emphasis markers, keeps link text, collapses whitespace
+ */
+export function stripMarkdown(text: string): string {
+    return text
+        .replace(/<!--[\s\S]*?-->/g, '')
+        .replace(/!?\[([^\]]*)\]\([^)]*\)/g, '$1')
+        .replace(/(\*\*|__|==|~~)(.+?)\1/g, '$2')
+        .replace(/(^|[^\w*])[*_]([^*_\n]+)[*_](?=[^\w*]|$)/g, '$1$2')
+        .replace(/[ \t]+/g, ' ')
+        .replace(/ *\n */g, '\n')
+        .replace(/\n{3,}/g, '\n\n')
+        .trim();
+}
+
+/**
+ * Human-readable note title: frontmatter `title`, else the first H1, else the file name
+ */
+export function extractTitle(content: string, filePath: string): string {
+    const lines = normalizeNewlines(content).split('\n');
+    let bodyStart = 0;
+
+    if (lines[0]?.trim() === '---') {
+        const end = lines.findIndex((line, i) => i > 0 && line.trim() === '---');
+        if (end !== -1) {
+            for (const line of lines.slice(1, end)) {
+                const match = line.match(/^title:\s*(.+?)\s*$/);
+                const title = match?.[1].replace(/^(["'])(.*)\1$/, '$2').trim();
+                if (title) {
+                    return title;
+                }
+            }
+            bodyStart = end + 1;
+        }
+    }
+
+    let inFence = false;
+    for (const line of lines.slice(bodyStart)) {
+        if (/^\s*(```|~~~)/.test(line)) {
+            inFence = !inFence;
+            continue;
+        }
+        const match = !inFence && line.match(/^#\s+(.+?)\s*#*\s*$/);
+        if (match) {
+            return match[1];
+        }
+    }
+
+    const fileName = filePath.split('/').pop() || filePath;
+    return fileName.replace(/\.[^.]+$/, '') || fileName;
+}
+
+/**
+ * Fast non-cryptographic 53-bit hash (cyrb53) as hex, for change detection only
+ */
+export function hashContent(text: string): string {
+    let h1 = 0xdeadbeef;
+    let h2 = 0x41c6ce57;
+    for (let i = 0; i < text.length; i++) {
+        const ch = text.charCodeAt(i);
+        h1 = Math.imul(h1 ^ ch, 2654435761);
+        h2 = Math.imul(h2 ^ ch, 1597334677);
+    }
+    h1 = Math.imul(h1 ^ (h1 >>> 16), 2246822507) ^ Math.imul(h2 ^ (h2 >>> 13), 3266489909);
+    h2 = Math.imul(h2 ^ (h2 >>> 16), 2246822507) ^ Math.imul(h1 ^ (h1 >>> 13), 3266489909);
+    return (4294967296 * (2097151 & h2) + (h1 >>> 0)).toString(16);
+}
@@ -10,22 +10,58 @@ import {
     ConceptExtractionConfig,
     ConceptFEConfig,
     ConceptSkipConfig,
+    QdrantTuningConfig,
+    IndexingConfig,
+    SearchConfig,
+    ScoreTransform,
+    WeakQueryBehavior,
+    ChunkOverflowPolicy,
+    VectorBackendKind,
     DEFAULT_EMBEDDING_CONFIG,
     DEFAULT_LLM_CONFIG,
     DEFAULT_CONCEPT_EXTRACTION_CONFIG,
     DEFAULT_CONCEPT_FE_CONFIG,
     DEFAULT_CONCEPT_SKIP_CONFIG,
+    DEFAULT_QDRANT_TUNING_CONFIG,
+    DEFAULT_INDEXING_CONFIG,
+    DEFAULT_SEARCH_CONFIG,
 } from '@core/types/setting';
+import { DEFAULT_EMBEDDING_MAX_INPUT_CHARS, DEFAULT_OLLAMA_KEEP_ALIVE } from '@core/types/embedding';
+import type { ChunkStrategyName, EmptySectionBehavior, OversizedPolicy } from '@core/types/indexing';
+import { parseHeaderLines } from '../services/embedding-service';
 import { getErrorMessage } from '@utils/error';
+import { getBuildInfo } from '@utils/build-info';
+import { confirmsCollection } from '@utils/confirmation';
 
 export interface MemoEchoSettings {
     // Model configs
     embeddingConfig: BaseModelConfig;
     llmConfig: BaseModelConfig;
 
+    // Vector store: Qdrant, or in memory (not persisted)
+    vectorBackend: VectorBackendKind;
+
     // Qdrant settings
     qdrantUrl: string;
     qdrantCollection: string;
+    qdrantTimeoutMs: number;
+    qdrantConnectRetries: number;
+    qdrantTuning: QdrantTuningConfig;
+
+    // Ollama keep_alive for the embedding model
+    embeddingKeepAlive: string;
+
+    // Extra headers on every Ollama embedding request, e.g. for an auth proxy
+    embeddingHeaders: Record<string, string>;
+
+    // Embedding input limit in characters, 0 disables
+    embeddingMaxInputChars: number;
+
+    // Indexing config
+    indexing: IndexingConfig;
+
+    // Search config
+    search: SearchConfig;
 
     // Concept extraction configs (使用配置对象)
     enableConceptExtraction: boolean;
@@ -39,9 +75,30 @@ export const DEFAULT_SETTINGS: MemoEchoSettings = {
     embeddingConfig: DEFAULT_EMBEDDING_CONFIG,
     llmConfig: DEFAULT_LLM_CONFIG,
 
+    // Vector store: Qdrant, or in memory (not persisted)
+    vectorBackend: 'qdrant',
+
     // Qdrant settings
     qdrantUrl: 'http://localhost:6333',
     qdrantCollection: 'obsidian_notes',
+    qdrantTimeoutMs: 10000,
+    qdrantConnectRetries: 3,
+    qdrantTuning: DEFAULT_QDRANT_TUNING_CONFIG,
+
+    // Ollama keep_alive for the embedding model
+    embeddingKeepAlive: DEFAULT_OLLAMA_KEEP_ALIVE,
+
+    // Extra headers on every Ollama embedding request, e.g. for an auth proxy
+    embeddingHeaders: {},
+
+    // Embedding input limit in characters, 0 disables
+    embeddingMaxInputChars: DEFAULT_EMBEDDING_MAX_INPUT_CHARS,
+
+    // Indexing config
+    indexing: DEFAULT_INDEXING_CONFIG,
+
+    // Search config
+    search: DEFAULT_SEARCH_CONFIG,
 
     // Concept extraction configs
     conceptExtraction: DEFAULT_CONCEPT_EXTRACTION_CONFIG,
@@ -53,9 +110,19 @@ export const DEFAULT_SETTINGS: MemoEchoSettings = {
 
 };
 
+// Lines buffered before each append while exporting
+const EXPORT_FLUSH_LINES = 500;
+
+// Files between progress notices during a vault sync
+const SYNC_NOTICE_EVERY = 50;
+
+// Chunks between progress notices while re-embedding
+const REEMBED_NOTICE_EVERY = 500;
+
 export class MemoEchoSettingTab extends PluginSettingTab {
     plugin: MemoEchoPlugin;
     private isIndexing = false;
+    private destructiveConfirmation = ''; // Collection name typed to unlock clear and bulk delete
 
     constructor(app: App, plugin: MemoEchoPlugin) {
         super(app, plugin);
@@ -73,9 +140,108 @@ export class MemoEchoSettingTab extends PluginSettingTab {
         this.addAiGenerationSection(containerEl);
         this.addConceptSection(containerEl);
         this.addIndexingSection(containerEl);
+        this.addSearchSection(containerEl);
         this.addDatabaseActionsSection(containerEl);
     }
 
+    private addSearchSection(containerEl: HTMLElement): void {
+        containerEl.createEl('h3', { text: '搜索' });
+
+        const group = containerEl.createDiv('memo-echo-settings-group');
+
+        new Setting(group)
+            .setName('相似度显示方式')
+            .setDesc('原始分数常集中在较窄区间。最小-最大: 按当前结果页拉伸到 0–100；排名: 按名次均匀分布')
+            .addDropdown(dropdown => dropdown
+                .addOption('raw', '原始分数')
+                .addOption('minmax', '最小-最大')
+                .addOption('rank', '排名')
+                .setValue(this.plugin.settings.search?.scoreTransform ?? DEFAULT_SEARCH_CONFIG.scoreTransform)
+                .onChange(async (value) => {
+                    const result = await this.plugin.settingsManager.updateSearch({ scoreTransform: value as ScoreTransform });
+                    this.handleSettingsResult(result);
+                }));
+
+        new Setting(group)
+            .setName('结果数量上限')
+            .setDesc('单次搜索最多返回的片段数')
+            .addText(text => text
+                .setPlaceholder(String(DEFAULT_SEARCH_CONFIG.maxResults))
+                .setValue(String(this.plugin.settings.search?.maxResults ?? DEFAULT_SEARCH_CONFIG.maxResults))
+                .onChange(async (value) => {
+                    const parsed = parseInt(value, 10);
+                    if (Number.isNaN(parsed) || parsed < 1) {
+                        return;
+                    }
+                    const result = await this.plugin.settingsManager.updateSearch({ maxResults: parsed });
+                    this.handleSettingsResult(result);
+                }));
+
+        new Setting(group)
+            .setName('弱查询处理')
+            .setDesc('有效词 (去除停用词后) 少于设定数量的查询视为弱查询：返回空结果，或扩大检索范围并标记结果。0 表示不检查')
+            .addText(text => text
+                .setPlaceholder(String(DEFAULT_SEARCH_CONFIG.minQueryTokens))
+                .setValue(String(this.plugin.settings.search?.minQueryTokens ?? DEFAULT_SEARCH_CONFIG.minQueryTokens))
+                .onChange(async (value) => {
+                    const parsed = parseInt(value, 10);
+                    if (Number.isNaN(parsed) || parsed < 0) {
+                        return;
+                    }
+                    const result = await this.plugin.settingsManager.updateSearch({ minQueryTokens: parsed });
+                    this.handleSettingsResult(result);
+                }))
+            .addDropdown(dropdown => dropdown
+                .addOption('empty', '返回空结果')
+                .addOption('widen', '扩大范围并标记')
+                .setValue(this.plugin.settings.search?.weakQueryBehavior ?? DEFAULT_SEARCH_CONFIG.weakQueryBehavior)
+                .onChange(async (value) => {
+                    const result = await this.plugin.settingsManager.updateSearch({ weakQueryBehavior: value as WeakQueryBehavior });
+                    this.handleSettingsResult(result);
+                }));
+
+        new Setting(group)
+            .setName('停用词')
+            .setDesc('逗号分隔，计算查询有效词时忽略')
+            .addTextArea(text => text
+                .setValue((this.plugin.settings.search?.stopWords ?? DEFAULT_SEARCH_CONFIG.stopWords).join(', '))
+                .onChange(async (value) => {
+                    const stopWords = value.split(/[,，\n]/).map(word => word.trim()).filter(Boolean);
+                    const result = await this.plugin.settingsManager.updateSearch({ stopWords });
+                    this.handleSettingsResult(result);
+                }));
+
+        new Setting(group)
+            .setName('新近度加权')
+            .setDesc('为最近索引的片段加分 (相对分数 + 权重 × 0.5^(天数 / 半衰期)，相对分数为与本次最佳结果的分数之比)，0 表示关闭。取值 0–1，建议 0.05–0.2')
+            .addText(text => text
+                .setPlaceholder('0')
+                .setValue(String(this.plugin.settings.search?.recencyBoost ?? DEFAULT_SEARCH_CONFIG.recencyBoost))
+                .onChange(async (value) => {
+                    const parsed = parseFloat(value);
+                    if (Number.isNaN(parsed) || parsed < 0 || parsed > 1) {
+                        return;
+                    }
+                    const result = await this.plugin.settingsManager.updateSearch({ recencyBoost: parsed });
+                    this.handleSettingsResult(result);
+                }));
+
+        new Setting(group)
+            .setName('新近度半衰期 (天)')
+            .setDesc('片段的新近度加分每经过该天数减半')
+            .addText(text => text
+                .setPlaceholder(String(DEFAULT_SEARCH_CONFIG.recencyHalfLifeDays))
+                .setValue(String(this.plugin.settings.search?.recencyHalfLifeDays ?? DEFAULT_SEARCH_CONFIG.recencyHalfLifeDays))
+                .onChange(async (value) => {
+                    const parsed = parseFloat(value);
+                    if (Number.isNaN(parsed) || parsed <= 0) {
+                        return;
+                    }
+                    const result = await this.plugin.settingsManager.updateSearch({ recencyHalfLifeDays: parsed });
+                    this.handleSettingsResult(result);
+                }));
+    }
+
     /**
      * Helper method to handle settings update results and show notices
      */
@@ -140,6 +306,272 @@ export class MemoEchoSettingTab extends PluginSettingTab {
                 .onClick(async () => {
                     await this.syncAllFiles();
                 }));
+
+        new Setting(group)
+            .setName('嵌入时包含标题路径')
+            .setDesc('生成向量时在内容前加上标题层级 (如 "# H1 > ## H2")，存储的内容不变。修改后需重新索引')
+            .addToggle(toggle => toggle
+                .setValue(this.plugin.settings.indexing.embedWithHeaderPath)
+                .onChange(async (value) => {
+                    const result = await this.plugin.settingsManager.updateIndexing({ embedWithHeaderPath: value });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }));
+
+        new Setting(group)
+            .setName('嵌入时包含 Callout 标题')
+            .setDesc('生成向量时在内容前加上块内 Callout 的类型和标题 (如 "tip: 缓存键")，存储的内容不变。修改后需重新索引')
+            .addToggle(toggle => toggle
+                .setValue(this.plugin.settings.indexing.embedCalloutTitles ?? DEFAULT_INDEXING_CONFIG.embedCalloutTitles)
+                .onChange(async (value) => {
+                    const result = await this.plugin.settingsManager.updateIndexing({ embedCalloutTitles: value });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }));
+
+        new Setting(group)
+            .setName('嵌入前清理 Markdown 标记')
+            .setDesc('生成向量时去除加粗/高亮标记、链接地址和注释，存储的内容不变。修改后需重新索引')
+            .addToggle(toggle => toggle
+                .setValue(this.plugin.settings.indexing.stripMarkdown)
+                .onChange(async (value) => {
+                    const result = await this.plugin.settingsManager.updateIndexing({ stripMarkdown: value });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }));
+
+        new Setting(group)
+            .setName('纯文本扩展名')
+            .setDesc('按纯文本索引的文件扩展名 (不解析标题)，逗号分隔，如: txt, org')
+            .addText(text => text
+                .setPlaceholder('txt')
+                .setValue(this.plugin.settings.indexing.plainTextExtensions.join(', '))
+                .onChange(async (value) => {
+                    const extensions = value
+                        .split(',')
+                        .map(ext => ext.trim().replace(/^\./, '').toLowerCase())
+                        .filter(ext => ext.length > 0 && ext !== 'md');
+                    const result = await this.plugin.settingsManager.updateIndexing({ plainTextExtensions: extensions });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }));
+
+        new Setting(group)
+            .setName('路径忽略大小写')
+            .setDesc('删除和重命名时按忽略大小写的路径匹配 (适用于不区分大小写的文件系统)。旧数据需重新索引后生效')
+            .addToggle(toggle => toggle
+                .setValue(this.plugin.settings.indexing.caseInsensitivePaths)
+                .onChange(async (value) => {
+                    const result = await this.plugin.settingsManager.updateIndexing({ caseInsensitivePaths: value });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }));
+
+        new Setting(group)
+            .setName('标题单独索引')
+            .setDesc('为每个标题额外生成一个只含标题文字的向量，便于按章节标题搜索。修改后需重新索引')
+            .addToggle(toggle => toggle
+                .setValue(this.plugin.settings.indexing.indexHeadings ?? false)
+                .onChange(async (value) => {
+                    const result = await this.plugin.settingsManager.updateIndexing({ indexHeadings: value });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }));
+
+        new Setting(group)
+            .setName('段落多向量 (高级)')
+            .setDesc('为长片段的每个段落额外生成向量，搜索时按最匹配的段落打分，提升长片段召回，但索引更慢、占用更多空间。需清空数据库后重新索引才会生效')
+            .addToggle(toggle => toggle
+                .setValue(this.plugin.settings.indexing.multiVector ?? DEFAULT_INDEXING_CONFIG.multiVector)
+                .onChange(async (value) => {
+                    const result = await this.plugin.settingsManager.updateIndexing({ multiVector: value });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }));
+
+        new Setting(group)
+            .setName('跳过未变化的文件')
+            .setDesc('同步时跳过内容和分块设置都未变化的文件，不重新计算向量。关闭后每次同步都重新索引全部文件')
+            .addToggle(toggle => toggle
+                .setValue(this.plugin.settings.indexing.skipUnchangedFiles ?? DEFAULT_INDEXING_CONFIG.skipUnchangedFiles)
+                .onChange(async (value) => {
+                    const result = await this.plugin.settingsManager.updateIndexing({ skipUnchangedFiles: value });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }));
+
+        new Setting(group)
+            .setName('片段长度上限')
+            .setDesc('按规则分块时单个片段的最大字符数 (至少 100)，修改后立即用于后续索引，已有索引需重新同步')
+            .addText(text => text
+                .setPlaceholder(String(DEFAULT_INDEXING_CONFIG.maxChunkSize))
+                .setValue(String(this.plugin.settings.indexing.maxChunkSize ?? DEFAULT_INDEXING_CONFIG.maxChunkSize))
+                .onChange(async (value) => {
+                    const parsed = parseInt(value, 10);
+                    if (Number.isNaN(parsed) || parsed < 100) {
+                        return;
+                    }
+                    const result = await this.plugin.settingsManager.updateIndexing({ maxChunkSize: parsed });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }));
+
+        new Setting(group)
+            .setName('分块方式')
+            .setDesc('Markdown 笔记的分块策略 (纯文本文件始终按长度分块)。修改后需重新索引')
+            .addDropdown(dropdown => dropdown
+                .addOption('header', '按标题 (适合文档)')
+                .addOption('paragraph', '按段落 (适合散文)')
+                .addOption('fixed', '按长度 (适合日志)')
+                .setValue(this.plugin.settings.indexing.chunkStrategy ?? DEFAULT_INDEXING_CONFIG.chunkStrategy)
+                .onChange(async (value) => {
+                    const result = await this.plugin.settingsManager.updateIndexing({ chunkStrategy: value as ChunkStrategyName });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }));
+
+        new Setting(group)
+            .setName('片段包含标题行')
+            .setDesc('关闭后片段内容从标题下一行开始，标题只保留在标题路径中，使向量更聚焦正文。修改后需重新索引')
+            .addToggle(toggle => toggle
+                .setValue(this.plugin.settings.indexing.includeHeadingInContent ?? true)
+                .onChange(async (value) => {
+                    const result = await this.plugin.settingsManager.updateIndexing({ includeHeadingInContent: value });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }));
+
+        new Setting(group)
+            .setName('空章节处理')
+            .setDesc('两个标题之间没有正文时的处理方式 (仅在片段包含标题行时生效)。修改后需重新索引')
+            .addDropdown(dropdown => dropdown
+                .addOption('keep', '保留为单独片段')
+                .addOption('merge', '并入下一个章节')
+                .addOption('skip', '跳过')
+                .setValue(this.plugin.settings.indexing.emptySections ?? DEFAULT_INDEXING_CONFIG.emptySections)
+                .onChange(async (value) => {
+                    const result = await this.plugin.settingsManager.updateIndexing({ emptySections: value as EmptySectionBehavior });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }));
+
+        new Setting(group)
+            .setName('超长无空白行')
+            .setDesc('超过片段长度上限且不含空白的行 (如 base64 数据) 的处理方式。修改后需重新索引')
+            .addDropdown(dropdown => dropdown
+                .addOption('split', '按长度硬切分')
+                .addOption('skip', '跳过不索引')
+                .addOption('placeholder', '以占位文本索引')
+                .setValue(this.plugin.settings.indexing.oversizedPolicy ?? DEFAULT_INDEXING_CONFIG.oversizedPolicy)
+                .onChange(async (value) => {
+                    const result = await this.plugin.settingsManager.updateIndexing({ oversizedPolicy: value as OversizedPolicy });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }));
+
+        new Setting(group)
+            .setName('按分隔线切分')
+            .setDesc('将章节内的分隔线 (---、***、___) 视为片段边界，拆出的片段共享同一标题路径。修改后需重新索引')
+            .addToggle(toggle => toggle
+                .setValue(this.plugin.settings.indexing.splitOnHr ?? false)
+                .onChange(async (value) => {
+                    const result = await this.plugin.settingsManager.updateIndexing({ splitOnHr: value });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }));
+
+        new Setting(group)
+            .setName('单文件片段上限')
+            .setDesc('超长文件 (如生成的日志) 最多生成的片段数，0 表示不限制')
+            .addText(text => text
+                .setPlaceholder(String(DEFAULT_INDEXING_CONFIG.maxChunksPerDoc))
+                .setValue(String(this.plugin.settings.indexing.maxChunksPerDoc ?? DEFAULT_INDEXING_CONFIG.maxChunksPerDoc))
+                .onChange(async (value) => {
+                    const parsed = parseInt(value, 10);
+                    if (Number.isNaN(parsed) || parsed < 0) {
+                        return;
+                    }
+                    const result = await this.plugin.settingsManager.updateIndexing({ maxChunksPerDoc: parsed });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }))
+            .addDropdown(dropdown => dropdown
+                .addOption('merge', '合并到最后一个片段')
+                .addOption('truncate', '丢弃超出部分')
+                .setValue(this.plugin.settings.indexing.chunkOverflowPolicy ?? DEFAULT_INDEXING_CONFIG.chunkOverflowPolicy)
+                .onChange(async (value) => {
+                    const result = await this.plugin.settingsManager.updateIndexing({ chunkOverflowPolicy: value as ChunkOverflowPolicy });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }));
+
+        new Setting(group)
+            .setName('同步并发数')
+            .setDesc('同步 Vault 时同时索引的文件数，过大会增加内存占用和嵌入服务压力')
+            .addText(text => text
+                .setPlaceholder(String(DEFAULT_INDEXING_CONFIG.indexConcurrency))
+                .setValue(String(this.plugin.settings.indexing.indexConcurrency ?? DEFAULT_INDEXING_CONFIG.indexConcurrency))
+                .onChange(async (value) => {
+                    const parsed = parseInt(value, 10);
+                    if (Number.isNaN(parsed) || parsed < 1) {
+                        return;
+                    }
+                    const result = await this.plugin.settingsManager.updateIndexing({ indexConcurrency: parsed });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }));
+
+        new Setting(group)
+            .setName('写入批大小')
+            .setDesc('累积多少个片段后批量写入向量库；每个文件索引完成时也会写入')
+            .addText(text => text
+                .setPlaceholder(String(DEFAULT_INDEXING_CONFIG.persistBatchSize))
+                .setValue(String(this.plugin.settings.indexing.persistBatchSize ?? DEFAULT_INDEXING_CONFIG.persistBatchSize))
+                .onChange(async (value) => {
+                    const parsed = parseInt(value, 10);
+                    if (Number.isNaN(parsed) || parsed < 1) {
+                        return;
+                    }
+                    const result = await this.plugin.settingsManager.updateIndexing({ persistBatchSize: parsed });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }));
+
+        const indexTimeoutMs = this.plugin.settings.indexing.indexTimeoutMs ?? DEFAULT_INDEXING_CONFIG.indexTimeoutMs;
+        new Setting(group)
+            .setName('单文件索引超时 (秒)')
+            .setDesc('单个文件索引超过该时间即中止，丢弃本次已生成的片段 (包括已写入的) 并保留上一次的索引。长笔记需逐片段提取元数据，请留足时间。0 表示不限制')
+            .addText(text => text
+                .setPlaceholder(String(DEFAULT_INDEXING_CONFIG.indexTimeoutMs / 1000))
+                .setValue((indexTimeoutMs / 1000).toString())
+                .onChange(async (value) => {
+                    const parsed = parseInt(value, 10);
+                    if (Number.isNaN(parsed) || parsed < 0) {
+                        return;
+                    }
+                    const result = await this.plugin.settingsManager.updateIndexing({ indexTimeoutMs: parsed * 1000 });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }));
     }
 
     private async indexCurrentFile() {
@@ -155,15 +587,15 @@ export class MemoEchoSettingTab extends PluginSettingTab {
             return;
         }
 
-        if (activeFile.extension !== 'md') {
-            new Notice('❌ 只支持 Markdown 文件');
+        if (!this.plugin.indexManager.isIndexable(activeFile.path)) {
+            new Notice('❌ 不支持的文件类型');
             return;
         }
 
         this.isIndexing = true;
 
         try {
-            new Notice('🔄 正在索引文件...');
+            const progressNotice = new Notice('🔄 正在索引文件...', 0);
             console.log(`\n========== 开始索引文件 ==========`);
             console.log(`📄 文件: ${activeFile.path}`);
 
@@ -171,11 +603,21 @@ export class MemoEchoSettingTab extends PluginSettingTab {
             const content = await this.app.vault.read(activeFile);
 
             // Use indexManager.indexFile() (v0.5.0)
-            await this.plugin.indexManager.indexFile(activeFile.path, content);
-            await this.plugin.indexManager.flush();
+            let capped = false;
+            try {
+                await this.plugin.indexManager.indexFile(activeFile.path, content, (progress) => {
+                    capped = progress.capped;
+                    progressNotice.setMessage(`🔄 正在索引文件: ${progress.embedded}/${progress.total} 个片段`);
+                });
+                await this.plugin.indexManager.flush();
+            } finally {
+                progressNotice.hide();
+            }
 
             console.log(`\n========== 索引完成 ==========`);
-            new Notice(`✅ 文件已索引`);
+            new Notice(capped
+                ? `⚠️ 文件已索引，但片段数超过上限 (${this.plugin.settings.indexing.maxChunksPerDoc})，超出部分已${this.plugin.settings.indexing.chunkOverflowPolicy === 'merge' ? '合并' : '丢弃'}`
+                : `✅ 文件已索引`);
 
             // Refresh stats
             this.display();
@@ -209,37 +651,25 @@ export class MemoEchoSettingTab extends PluginSettingTab {
         try {
             new Notice('🔄 开始同步 Vault...');
 
-            const files = this.app.vault.getMarkdownFiles();
-            let indexed = 0;
-            let failed = 0;
-
-            for (let i = 0; i < files.length; i++) {
-                const file = files[i];
-
-                try {
-                    // Show progress every 10 files
-                    if (i % 10 === 0) {
-                        new Notice(`同步中: ${i}/${files.length} 文件...`);
+            const paths = this.app.vault.getFiles()
+                .filter(file => this.plugin.indexManager.isIndexable(file.path))
+                .map(file => file.path);
+
+            // Files are read lazily by the worker pool; pending chunks are flushed at the end
+            const report = await this.plugin.indexManager.indexPaths(
+                paths,
+                (path) => this.app.vault.adapter.read(path),
+                ({ done, total }) => {
+                    if (done % SYNC_NOTICE_EVERY === 0) {
+                        new Notice(`同步中: ${done}/${total} 文件...`);
                     }
-
-                    // Read and index using indexManager (v0.5.0)
-                    const content = await this.app.vault.read(file);
-                    await this.plugin.indexManager.updateFile(file.path, content);
-                    indexed++;
-
-                } catch (error) {
-                    console.error(`Failed to sync ${file.path}:`, error);
-                    failed++;
                 }
-            }
-
-            // Flush all pending chunks
-            await this.plugin.indexManager.flush();
+            );
 
             new Notice(
                 `✅ 同步完成!\n\n` +
-                `已索引: ${indexed} 个文件\n` +
-                `失败: ${failed}`,
+                `已索引: ${report.indexed} 个文件 (未变化跳过 ${report.unchanged})\n` +
+                `失败: ${report.failed}`,
                 10000
             );
 
@@ -267,13 +697,50 @@ export class MemoEchoSettingTab extends PluginSettingTab {
                 .setButtonText('检查连接')
                 .onClick(async () => {
                     try {
-                        const count = await this.plugin.vectorBackend.count();
-                        new Notice(`✅ Qdrant 已连接 (${count} 个向量)`);
+                        const health = await this.plugin.vectorBackend.getHealth();
+                        if (!health.connected) {
+                            new Notice('❌ Qdrant 连接失败，请确保 Qdrant 已启动');
+                            return;
+                        }
+
+                        const model = this.plugin.embeddingService.getModelName();
+                        const dimension = this.plugin.embeddingService.getDimension();
+                        new Notice(
+                            `✅ Qdrant 已连接 (${health.pointsCount ?? '未知数量'} 个向量)\n` +
+                            `集合: ${health.collectionName}\n` +
+                            `模型: ${model} (${dimension} 维)`
+                        );
+
+                        if (health.dimension !== null && health.dimension !== dimension) {
+                            new Notice(
+                                `⚠️ 维度不匹配: 集合为 ${health.dimension} 维，当前模型为 ${dimension} 维。` +
+                                '请更换模型或清空数据库后重新索引',
+                                10000
+                            );
+                        }
                     } catch (error) {
                         new Notice(`❌ Qdrant 连接失败: ${getErrorMessage(error)}`);
                     }
                 }));
 
+        new Setting(statusContainer)
+            .setName('版本信息')
+            .setDesc(`v${this.plugin.manifest.version}，反馈问题时可附上 (不含密钥)`)
+            .addButton(button => button
+                .setButtonText('复制')
+                .onClick(async () => {
+                    const info = getBuildInfo(this.plugin.manifest.version, {
+                        qdrantUrl: this.plugin.settings.qdrantUrl,
+                        embeddingUrl: this.plugin.settings.embeddingConfig.baseUrl,
+                        embeddingModel: this.plugin.embeddingService.getModelName(),
+                    });
+                    try {
+                        await navigator.clipboard.writeText(JSON.stringify(info, null, 2));
+                        new Notice('✅ 版本信息已复制');
+                    } catch (error) {
+                        new Notice(`❌ 复制失败: ${getErrorMessage(error)}`);
+                    }
+                }));
     }
 
     private addEmbeddingSection(containerEl: HTMLElement): void {
@@ -359,8 +826,53 @@ export class MemoEchoSettingTab extends PluginSettingTab {
                 .onClick(() => {
                     this.display();
                 }));
+
+            new Setting(containerEl)
+                .setName('模型保持加载时间 (keep_alive)')
+                .setDesc('Ollama 在最后一次请求后保留模型的时间，如 30m、1h；0 表示立即卸载')
+                .addText(text => text
+                    .setPlaceholder(DEFAULT_OLLAMA_KEEP_ALIVE)
+                    .setValue(this.plugin.settings.embeddingKeepAlive)
+                    .onChange(async (value) => {
+                        const result = await this.plugin.settingsManager.updateEmbeddingRequest({
+                            embeddingKeepAlive: value.trim() || DEFAULT_OLLAMA_KEEP_ALIVE,
+                        });
+                        this.handleSettingsResult(result);
+                    }));
+
+            new Setting(containerEl)
+                .setName('自定义请求头')
+                .setDesc('每行一个 "名称: 值"，随每个 Ollama 请求发送，用于需要认证的反向代理，如 Authorization: Bearer xxx')
+                .addTextArea(text => text
+                    .setPlaceholder('X-Api-Key: your-key')
+                    .setValue(Object.keys(this.plugin.settings.embeddingHeaders ?? {})
+                        .map(name => `${name}: ${this.plugin.settings.embeddingHeaders[name]}`)
+                        .join('\n'))
+                    .onChange(async (value) => {
+                        const result = await this.plugin.settingsManager.updateEmbeddingRequest({
+                            embeddingHeaders: parseHeaderLines(value),
+                        });
+                        this.handleSettingsResult(result);
+                    }));
         }
 
+        new Setting(containerEl)
+            .setName('嵌入输入长度上限')
+            .setDesc('超过该字符数的文本在句子或词边界处截断后再生成向量，避免超出模型上下文。0 表示不限制')
+            .addText(text => text
+                .setPlaceholder(String(DEFAULT_EMBEDDING_MAX_INPUT_CHARS))
+                .setValue(String(this.plugin.settings.embeddingMaxInputChars ?? DEFAULT_EMBEDDING_MAX_INPUT_CHARS))
+                .onChange(async (value) => {
+                    const parsed = parseInt(value, 10);
+                    if (Number.isNaN(parsed)) {
+                        return;
+                    }
+                    const result = await this.plugin.settingsManager.updateEmbeddingRequest({
+                        embeddingMaxInputChars: parsed,
+                    });
+                    this.handleSettingsResult(result);
+                }));
+
         if (config.provider === 'openai') {
             // OpenAI settings
             new Setting(containerEl)
@@ -514,6 +1026,18 @@ export class MemoEchoSettingTab extends PluginSettingTab {
     }
 
     private addQdrantSection(containerEl: HTMLElement): void {
+        new Setting(containerEl)
+            .setName('向量存储')
+            .setDesc('内存存储不需要 Qdrant，但索引不会保存，重启后需重新索引 (修改后需重启插件)')
+            .addDropdown(dropdown => dropdown
+                .addOption('qdrant', 'Qdrant')
+                .addOption('memory', '内存 (测试或小型库)')
+                .setValue(this.plugin.settings.vectorBackend ?? DEFAULT_SETTINGS.vectorBackend)
+                .onChange(async (value) => {
+                    this.plugin.settings.vectorBackend = value as VectorBackendKind;
+                    await this.plugin.saveSettings();
+                }));
+
         containerEl.createEl('h4', { text: 'Qdrant 设置' });
 
         new Setting(containerEl)
@@ -537,6 +1061,174 @@ export class MemoEchoSettingTab extends PluginSettingTab {
                     this.plugin.settings.qdrantCollection = value;
                     await this.plugin.saveSettings();
                 }));
+
+        new Setting(containerEl)
+            .setName('请求超时 (毫秒)')
+            .setDesc('单次 Qdrant 请求的超时时间 (修改后需重启插件)')
+            .addText(text => text
+                .setPlaceholder('10000')
+                .setValue(this.plugin.settings.qdrantTimeoutMs.toString())
+                .onChange(async (value) => {
+                    const parsed = parseInt(value, 10);
+                    if (!Number.isNaN(parsed) && parsed > 0) {
+                        this.plugin.settings.qdrantTimeoutMs = parsed;
+                        await this.plugin.saveSettings();
+                    }
+                }));
+
+        new Setting(containerEl)
+            .setName('启动重试次数')
+            .setDesc('插件启动时 Qdrant 未就绪的重试次数，间隔逐次翻倍 (修改后需重启插件)')
+            .addText(text => text
+                .setPlaceholder('3')
+                .setValue(this.plugin.settings.qdrantConnectRetries.toString())
+                .onChange(async (value) => {
+                    const parsed = parseInt(value, 10);
+                    if (!Number.isNaN(parsed) && parsed >= 0) {
+                        this.plugin.settings.qdrantConnectRetries = parsed;
+                        await this.plugin.saveSettings();
+                    }
+                }));
+
+        const tuning = this.plugin.settings.qdrantTuning;
+
+        new Setting(containerEl)
+            .setName('HNSW m')
+            .setDesc('索引图的连接数 (4-64)，越大召回越高、内存越多 (仅在创建集合时生效)')
+            .addText(text => text
+                .setPlaceholder('16')
+                .setValue(tuning.hnswM.toString())
+                .onChange(async (value) => {
+                    const parsed = Number(value);
+                    if (!Number.isNaN(parsed)) {
+                        const result = await this.plugin.settingsManager.updateQdrantTuning({ hnswM: parsed });
+                        if (!result.success) {
+                            new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                        }
+                    }
+                }));
+
+        new Setting(containerEl)
+            .setName('HNSW ef_construct')
+            .setDesc('构建索引时的搜索深度 (4-1000)，越大索引质量越高、构建越慢 (仅在创建集合时生效)')
+            .addText(text => text
+                .setPlaceholder('100')
+                .setValue(tuning.hnswEfConstruct.toString())
+                .onChange(async (value) => {
+                    const parsed = Number(value);
+                    if (!Number.isNaN(parsed)) {
+                        const result = await this.plugin.settingsManager.updateQdrantTuning({ hnswEfConstruct: parsed });
+                        if (!result.success) {
+                            new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                        }
+                    }
+                }));
+
+        new Setting(containerEl)
+            .setName('Payload 存储在磁盘')
+            .setDesc('减少内存占用，过滤时稍慢 (仅在创建集合时生效)')
+            .addToggle(toggle => toggle
+                .setValue(tuning.onDiskPayload)
+                .onChange(async (value) => {
+                    const result = await this.plugin.settingsManager.updateQdrantTuning({ onDiskPayload: value });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }));
+
+        new Setting(containerEl)
+            .setName('标量量化 (int8)')
+            .setDesc('将向量压缩为 int8，向量内存约减少为 1/4，召回率略有下降 (搜索时用原始向量重排)。适合数十万片段的大型库 (仅在创建集合时生效)')
+            .addToggle(toggle => toggle
+                .setValue(tuning.scalarQuantization ?? DEFAULT_QDRANT_TUNING_CONFIG.scalarQuantization)
+                .onChange(async (value) => {
+                    const result = await this.plugin.settingsManager.updateQdrantTuning({ scalarQuantization: value });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }));
+
+        new Setting(containerEl)
+            .setName('量化分位数')
+            .setDesc('落在 int8 范围内的数值比例 (0.5-1)，其余异常值被截断。越低精度越集中、异常值误差越大 (仅在创建集合时生效)')
+            .addText(text => text
+                .setPlaceholder(DEFAULT_QDRANT_TUNING_CONFIG.quantizationQuantile.toString())
+                .setValue((tuning.quantizationQuantile ?? DEFAULT_QDRANT_TUNING_CONFIG.quantizationQuantile).toString())
+                .onChange(async (value) => {
+                    const parsed = Number(value);
+                    if (value.trim() && !Number.isNaN(parsed)) {
+                        const result = await this.plugin.settingsManager.updateQdrantTuning({ quantizationQuantile: parsed });
+                        if (!result.success) {
+                            new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                        }
+                    }
+                }));
+
+        new Setting(containerEl)
+            .setName('量化向量常驻内存')
+            .setDesc('即使原始向量在磁盘上，也将量化向量保留在内存中以保证搜索速度 (仅在创建集合时生效)')
+            .addToggle(toggle => toggle
+                .setValue(tuning.quantizationAlwaysRam ?? DEFAULT_QDRANT_TUNING_CONFIG.quantizationAlwaysRam)
+                .onChange(async (value) => {
+                    const result = await this.plugin.settingsManager.updateQdrantTuning({ quantizationAlwaysRam: value });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }));
+
+        new Setting(containerEl)
+            .setName('索引阈值 (KB)')
+            .setDesc('分段内向量超过该大小后才构建 HNSW 索引，0 表示不构建。点击优化时生效')
+            .addText(text => text
+                .setPlaceholder(DEFAULT_QDRANT_TUNING_CONFIG.indexingThreshold.toString())
+                .setValue((tuning.indexingThreshold ?? DEFAULT_QDRANT_TUNING_CONFIG.indexingThreshold).toString())
+                .onChange(async (value) => {
+                    const parsed = Number(value);
+                    if (!Number.isNaN(parsed)) {
+                        const result = await this.plugin.settingsManager.updateQdrantTuning({ indexingThreshold: parsed });
+                        if (!result.success) {
+                            new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                        }
+                    }
+                }))
+            .addButton(button => button
+                .setButtonText('立即优化')
+                .onClick(async () => {
+                    try {
+                        const status = await this.plugin.vectorBackend.optimize();
+                        new Notice(`✅ 已请求优化${status ? ` (集合状态: ${status})` : ''}`);
+                    } catch (error) {
+                        new Notice(`❌ 优化失败: ${getErrorMessage(error)}`);
+                    }
+                }));
+
+        new Setting(containerEl)
+            .setName('批量写入后自动优化')
+            .setDesc('导入索引或重新生成向量后请求 Qdrant 优化索引，避免短时间内搜索变慢')
+            .addToggle(toggle => toggle
+                .setValue(tuning.optimizeAfterBulkWrites ?? DEFAULT_QDRANT_TUNING_CONFIG.optimizeAfterBulkWrites)
+                .onChange(async (value) => {
+                    const result = await this.plugin.settingsManager.updateQdrantTuning({ optimizeAfterBulkWrites: value });
+                    if (!result.success) {
+                        new Notice(`❌ 更新失败: ${result.errors?.[0]?.message}`);
+                    }
+                }));
+    }
+
+    /**
+     * Request optimization after a bulk write when enabled; failures only log, the write already succeeded
+     */
+    private async optimizeAfterBulkWrite(): Promise<void> {
+        const enabled = this.plugin.settings.qdrantTuning.optimizeAfterBulkWrites
+            ?? DEFAULT_QDRANT_TUNING_CONFIG.optimizeAfterBulkWrites;
+        if (!enabled) {
+            return;
+        }
+        try {
+            await this.plugin.vectorBackend.optimize();
+        } catch (error) {
+            console.warn('批量写入后优化失败:', error);
+        }
     }
 
     // v0.5.0: Concept Injection Settings Section
@@ -759,6 +1451,17 @@ export class MemoEchoSettingTab extends PluginSettingTab {
 
         const group = containerEl.createDiv('memo-echo-settings-group');
 
+        // Typed once, checked by every destructive action below
+        this.destructiveConfirmation = '';
+        new Setting(group)
+            .setName('确认集合名称')
+            .setDesc(`清空数据库、按标签删除和删除概念前，需在此输入集合名称 "${this.plugin.settings.qdrantCollection}"`)
+            .addText(text => text
+                .setPlaceholder(this.plugin.settings.qdrantCollection)
+                .onChange((value) => {
+                    this.destructiveConfirmation = value;
+                }));
+
         // Clear database button
         new Setting(group)
             .setName('清空数据库')
@@ -767,18 +1470,183 @@ export class MemoEchoSettingTab extends PluginSettingTab {
                 .setButtonText('清空数据库')
                 .setWarning()
                 .onClick(async () => {
-                    const confirmed = confirm(
-                        '⚠️ 确定要清空所有向量数据吗?\n\n此操作不可撤销!'
-                    );
+                    if (!this.checkDestructiveConfirmation()) {
+                        return;
+                    }
 
-                    if (confirmed) {
-                        try {
-                            await this.plugin.vectorBackend.clear();
-                            new Notice('✅ 数据库已清空');
-                            this.display();
-                        } catch (error) {
-                            new Notice(`❌ 清空失败: ${getErrorMessage(error)}`);
+                    try {
+                        // Recreate with the current model's dimension so a model switch rebuilds cleanly
+                        await this.plugin.vectorBackend.clear(this.plugin.embeddingService.getDimension());
+                        new Notice('✅ 数据库已清空');
+                        this.display();
+                    } catch (error) {
+                        new Notice(`❌ 清空失败: ${getErrorMessage(error)}`);
+                    }
+                }));
+
+        new Setting(group)
+            .setName('重新生成向量')
+            .setDesc('更换同维度的嵌入模型后，用已存储的片段内容重新计算全部向量，无需重新读取文件')
+            .addButton(button => button
+                .setButtonText('重新生成')
+                .onClick(async () => {
+                    if (this.isIndexing) {
+                        new Notice('正在索引中,请稍候...');
+                        return;
+                    }
+                    this.isIndexing = true;
+
+                    try {
+                        new Notice('🔄 开始重新生成向量...');
+                        let nextNotice = REEMBED_NOTICE_EVERY;
+                        const report = await this.plugin.indexManager.reembedAll(({ updated }) => {
+                            if (updated >= nextNotice) {
+                                new Notice(`重新生成中: 已完成 ${updated} 个片段...`);
+                                nextNotice += REEMBED_NOTICE_EVERY;
+                            }
+                        });
+                        new Notice(`✅ 已更新 ${report.updated} 个片段，失败 ${report.failed} 个`, 10000);
+                        await this.optimizeAfterBulkWrite();
+                    } catch (error) {
+                        new Notice(`❌ 重新生成失败: ${getErrorMessage(error)}`);
+                    } finally {
+                        this.isIndexing = false;
+                    }
+                }));
+
+        this.addExportSection(group);
+        this.addBulkDeleteSection(group);
+    }
+
+    /**
+     * Destructive actions need the collection name typed in the confirmation field, not just a click
+     */
+    private checkDestructiveConfirmation(): boolean {
+        const collection = this.plugin.settings.qdrantCollection;
+        if (confirmsCollection(this.destructiveConfirmation, collection)) {
+            return true;
+        }
+        new Notice(`❌ 请先在"确认集合名称"中输入 "${collection}"`);
+        return false;
+    }
+
+    private addExportSection(containerEl: HTMLElement): void {
+        let includeVectors = false;
+
+        new Setting(containerEl)
+            .setName('导出索引 (JSONL)')
+            .setDesc('将所有向量数据逐行导出到 Vault 根目录，用于备份或迁移。包含向量时文件较大')
+            .addToggle(toggle => toggle
+                .setTooltip('包含向量')
+                .setValue(includeVectors)
+                .onChange((value) => {
+                    includeVectors = value;
+                }))
+            .addButton(button => button
+                .setButtonText('导出')
+                .onClick(async () => {
+                    const path = `memo-echo-export-${Date.now()}.jsonl`;
+                    const adapter = this.app.vault.adapter;
+                    let buffer: string[] = [];
+
+                    try {
+                        await adapter.write(path, '');
+                        const count = await this.plugin.vectorBackend.exportPoints(async (line) => {
+                            buffer.push(line);
+                            if (buffer.length >= EXPORT_FLUSH_LINES) {
+                                await adapter.append(path, buffer.join('\n') + '\n');
+                                buffer = [];
+                            }
+                        }, { includeVectors });
+                        if (buffer.length > 0) {
+                            await adapter.append(path, buffer.join('\n') + '\n');
                         }
+                        new Notice(`✅ 已导出 ${count} 个向量到 ${path}`);
+                    } catch (error) {
+                        new Notice(`❌ 导出失败: ${getErrorMessage(error)}`);
+                    }
+                }));
+
+        let importPath = '';
+        new Setting(containerEl)
+            .setName('导入索引 (JSONL)')
+            .setDesc('从包含向量的导出文件恢复索引 (Vault 内路径)，维度不匹配的行会被跳过')
+            .addText(text => text
+                .setPlaceholder('memo-echo-export-xxx.jsonl')
+                .onChange((value) => {
+                    importPath = value.trim();
+                }))
+            .addButton(button => button
+                .setButtonText('导入')
+                .onClick(async () => {
+                    if (!importPath) {
+                        new Notice('❌ 请输入导出文件路径');
+                        return;
+                    }
+
+                    const recreate = confirm('导入前是否清空现有索引?\n\n确定: 清空后导入 (精确恢复)\n取消: 合并到现有索引');
+                    try {
+                        const content = await this.app.vault.adapter.read(importPath);
+                        const report = await this.plugin.vectorBackend.importPoints(content.split('\n'), { recreate });
+                        new Notice(`✅ 已导入 ${report.imported} 个向量，跳过 ${report.skipped} 行`);
+                        await this.optimizeAfterBulkWrite();
+                        this.display();
+                    } catch (error) {
+                        new Notice(`❌ 导入失败: ${getErrorMessage(error)}`);
+                    }
+                }));
+    }
+
+    private addBulkDeleteSection(containerEl: HTMLElement): void {
+        let tagToDelete = '';
+
+        new Setting(containerEl)
+            .setName('按标签删除')
+            .setDesc('删除带有指定标签的所有向量 (如 archive)')
+            .addText(text => text
+                .setPlaceholder('archive')
+                .onChange((value) => {
+                    tagToDelete = value.trim();
+                }))
+            .addButton(button => button
+                .setButtonText('删除')
+                .setWarning()
+                .onClick(async () => {
+                    if (!tagToDelete) {
+                        new Notice('❌ 请输入标签');
+                        return;
+                    }
+
+                    if (!this.checkDestructiveConfirmation()) {
+                        return;
+                    }
+
+                    try {
+                        const deleted = await this.plugin.vectorBackend.deleteByTag(tagToDelete);
+                        new Notice(`✅ 已删除 ${deleted} 个向量`);
+                        this.display();
+                    } catch (error) {
+                        new Notice(`❌ 删除失败: ${getErrorMessage(error)}`);
+                    }
+                }));
+
+        new Setting(containerEl)
+            .setName('删除所有概念')
+            .setDesc('删除概念注册表中的所有概念记录 (保留笔记片段)')
+            .addButton(button => button
+                .setButtonText('删除概念')
+                .setWarning()
+                .onClick(async () => {
+                    if (!this.checkDestructiveConfirmation()) {
+                        return;
+                    }
+
+                    try {
+                        const deleted = await this.plugin.vectorBackend.deleteByType('concept');
+                        new Notice(`✅ 已删除 ${deleted} 个概念`);
+                        this.display();
+                    } catch (error) {
+                        new Notice(`❌ 删除失败: ${getErrorMessage(error)}`);
                     }
                 }));
     }